@@ -0,0 +1,505 @@
+use std::borrow::Cow;
+use serde::{Serialize, Serializer};
+use serde::ser::SerializeMap;
+use crate::common::{Color, Font, Paint};
+use crate::options::PointStyle;
+use crate::plugin::{ChartPlugin, ScriptSource};
+use crate::serde::{settings, ValueSerializeWrapper, WithTypeAndSerializer};
+
+/// chartjs-plugin-annotation, loaded along with the chart when it has annotations.
+/// Version 3 is the one for Chart.js 4.
+pub const ANNOTATION_SCRIPT: &str = "https://cdn.jsdelivr.net/npm/chartjs-plugin-annotation@3";
+
+/// chartjs-plugin-annotation, added by charts that have annotations
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnnotationPlugin;
+
+impl ChartPlugin for AnnotationPlugin {
+    fn options_key(&self) -> Cow<'static, str> {
+        Cow::Borrowed("annotation")
+    }
+
+    fn scripts(&self) -> Vec<ScriptSource> {
+        vec![ANNOTATION_SCRIPT.into()]
+    }
+
+    ///the UMD build registers itself in recent versions, registering again is harmless
+    fn registration(&self) -> Option<Cow<'static, str>> {
+        Some(Cow::Borrowed("if (window['chartjs-plugin-annotation']) { Chart.register(window['chartjs-plugin-annotation']); }"))
+    }
+}
+
+/// Annotations drawn over the chart area, like threshold lines or shaded time ranges
+#[derive(Debug, Clone)]
+pub struct Annotations<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> {
+    annotations: Vec<(String, Annotation<X, Y>)>
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> Default for Annotations<X, Y> {
+    fn default() -> Self {
+        Self { annotations: vec![] }
+    }
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> Annotations<X, Y> {
+
+    ///`id` has to be unique within the chart, a second annotation with the same id replaces the first
+    pub fn with_annotation<A: Into<Annotation<X, Y>>>(mut self, id: &str, annotation: A) -> Self {
+        self.annotations.retain(|(existing, _)| existing != id);
+        self.annotations.push((id.to_string(), annotation.into()));
+        self
+    }
+}
+
+impl<X, Y> Serialize for Annotations<X, Y> where X: WithTypeAndSerializer + Serialize, Y: WithTypeAndSerializer + Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        struct ById<'a, X: WithTypeAndSerializer, Y: WithTypeAndSerializer>(&'a [(String, Annotation<X, Y>)]);
+
+        impl<X, Y> Serialize for ById<'_, X, Y> where X: WithTypeAndSerializer + Serialize, Y: WithTypeAndSerializer + Serialize {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+                serializer.collect_map(self.0.iter().map(|(id, annotation)| (id, annotation)))
+            }
+        }
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry("annotations", &ById(&self.annotations))?;
+        map.end()
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Annotation<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> {
+    Line(LineAnnotation<X, Y>),
+    Box(BoxAnnotation<X, Y>),
+    Point(PointAnnotation<X, Y>),
+    Label(LabelAnnotation<X, Y>),
+    Ellipse(BoxAnnotation<X, Y>)
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> Annotation<X, Y> {
+
+    ///ellipse filling the area of the box, `BoxAnnotation::into` gives a box annotation
+    pub fn ellipse(area: BoxAnnotation<X, Y>) -> Self {
+        Annotation::Ellipse(area)
+    }
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> From<LineAnnotation<X, Y>> for Annotation<X, Y> {
+    fn from(value: LineAnnotation<X, Y>) -> Self {
+        Annotation::Line(value)
+    }
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> From<BoxAnnotation<X, Y>> for Annotation<X, Y> {
+    fn from(value: BoxAnnotation<X, Y>) -> Self {
+        Annotation::Box(value)
+    }
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> From<PointAnnotation<X, Y>> for Annotation<X, Y> {
+    fn from(value: PointAnnotation<X, Y>) -> Self {
+        Annotation::Point(value)
+    }
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> From<LabelAnnotation<X, Y>> for Annotation<X, Y> {
+    fn from(value: LabelAnnotation<X, Y>) -> Self {
+        Annotation::Label(value)
+    }
+}
+
+/// Area an annotation covers, in axis values. Sides that are not set extend to the edge of the chart area.
+///
+/// With the `y` index axis the `X` values are on the vertical scale, keys are swapped to match.
+#[derive(Debug, Clone)]
+struct Extent<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> {
+    x_min: Option<ValueSerializeWrapper<X>>,
+    x_max: Option<ValueSerializeWrapper<X>>,
+    y_min: Option<ValueSerializeWrapper<Y>>,
+    y_max: Option<ValueSerializeWrapper<Y>>
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> Default for Extent<X, Y> {
+    fn default() -> Self {
+        Self { x_min: None, x_max: None, y_min: None, y_max: None }
+    }
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> Serialize for Extent<X, Y> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let (x_keys, y_keys) = if settings().transposed {
+            (("yMin", "yMax"), ("xMin", "xMax"))
+        } else {
+            (("xMin", "xMax"), ("yMin", "yMax"))
+        };
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(v) = &self.x_min { map.serialize_entry(x_keys.0, v)?; }
+        if let Some(v) = &self.x_max { map.serialize_entry(x_keys.1, v)?; }
+        if let Some(v) = &self.y_min { map.serialize_entry(y_keys.0, v)?; }
+        if let Some(v) = &self.y_max { map.serialize_entry(y_keys.1, v)?; }
+        map.end()
+    }
+}
+
+/// Single point an annotation is placed at, in axis values
+#[derive(Debug, Clone)]
+struct Anchor<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> {
+    x: ValueSerializeWrapper<X>,
+    y: ValueSerializeWrapper<Y>
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> Serialize for Anchor<X, Y> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let (x_key, y_key) = if settings().transposed { ("yValue", "xValue") } else { ("xValue", "yValue") };
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry(x_key, &self.x)?;
+        map.serialize_entry(y_key, &self.y)?;
+        map.end()
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelPosition {
+    Start,
+    Center,
+    End
+}
+
+/// Text shown on a line or box annotation
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationLabel {
+    display: bool,
+    ///one entry per line
+    content: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<LabelPosition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font: Option<Font>
+}
+
+impl AnnotationLabel {
+
+    pub fn new(text: &str) -> Self {
+        Self {
+            display: true,
+            content: text.lines().map(str::to_string).collect(),
+            position: None,
+            color: None,
+            background_color: None,
+            font: None
+        }
+    }
+
+    pub fn with_position(mut self, position: LabelPosition) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    pub fn with_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn with_background_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.background_color = Some(color.into());
+        self
+    }
+
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+}
+
+/// Straight line, across the whole chart area when only one axis value is given
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LineAnnotation<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> {
+    #[serde(flatten)]
+    extent: Extent<X, Y>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_width: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_dash: Option<Vec<f32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<AnnotationLabel>
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> LineAnnotation<X, Y> {
+
+    fn with_extent(extent: Extent<X, Y>) -> Self {
+        Self {
+            extent,
+            border_color: None,
+            border_width: None,
+            border_dash: None,
+            label: None
+        }
+    }
+
+    ///line across the chart at value `y`, like a threshold
+    pub fn horizontal(y: Y) -> Self where Y: Clone {
+        Self::with_extent(Extent { y_min: Some(y.clone().into()), y_max: Some(y.into()), ..Extent::default() })
+    }
+
+    ///line across the chart at value `x`, like a deploy marker
+    pub fn vertical(x: X) -> Self where X: Clone {
+        Self::with_extent(Extent { x_min: Some(x.clone().into()), x_max: Some(x.into()), ..Extent::default() })
+    }
+
+    ///line between two points
+    pub fn between(from: (X, Y), to: (X, Y)) -> Self {
+        Self::with_extent(Extent {
+            x_min: Some(from.0.into()),
+            y_min: Some(from.1.into()),
+            x_max: Some(to.0.into()),
+            y_max: Some(to.1.into())
+        })
+    }
+
+    pub fn with_border_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.border_color = Some(color.into());
+        self
+    }
+
+    pub fn with_border_width(mut self, width: f32) -> Self {
+        self.border_width = Some(width);
+        self
+    }
+
+    pub fn with_border_dash(mut self, dash: Vec<f32>) -> Self {
+        self.border_dash = Some(dash);
+        self
+    }
+
+    pub fn with_label(mut self, label: AnnotationLabel) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+/// Rectangle, or ellipse through `Annotation::Ellipse`; sides that are not set extend to the edge of the chart area
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxAnnotation<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> {
+    #[serde(flatten)]
+    extent: Extent<X, Y>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background_color: Option<Paint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_width: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_dash: Option<Vec<f32>>,
+    ///rotation in degrees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rotation: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<AnnotationLabel>
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> Default for BoxAnnotation<X, Y> {
+    fn default() -> Self {
+        Self {
+            extent: Extent::default(),
+            background_color: None,
+            border_color: None,
+            border_width: None,
+            border_dash: None,
+            rotation: None,
+            label: None
+        }
+    }
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> BoxAnnotation<X, Y> {
+
+    ///band over the full height of the chart, like a maintenance window
+    pub fn x_range(from: X, to: X) -> Self {
+        Self::default().with_x_range(from, to)
+    }
+
+    ///band over the full width of the chart, like an acceptable value range
+    pub fn y_range(from: Y, to: Y) -> Self {
+        Self::default().with_y_range(from, to)
+    }
+
+    pub fn with_x_range(mut self, from: X, to: X) -> Self {
+        self.extent.x_min = Some(from.into());
+        self.extent.x_max = Some(to.into());
+        self
+    }
+
+    pub fn with_y_range(mut self, from: Y, to: Y) -> Self {
+        self.extent.y_min = Some(from.into());
+        self.extent.y_max = Some(to.into());
+        self
+    }
+
+    pub fn with_background_color<C: Into<Paint>>(mut self, color: C) -> Self {
+        self.background_color = Some(color.into());
+        self
+    }
+
+    pub fn with_border_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.border_color = Some(color.into());
+        self
+    }
+
+    pub fn with_border_width(mut self, width: f32) -> Self {
+        self.border_width = Some(width);
+        self
+    }
+
+    pub fn with_border_dash(mut self, dash: Vec<f32>) -> Self {
+        self.border_dash = Some(dash);
+        self
+    }
+
+    pub fn with_rotation(mut self, degrees: f32) -> Self {
+        self.rotation = Some(degrees);
+        self
+    }
+
+    pub fn with_label(mut self, label: AnnotationLabel) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+/// Marker at a single point
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PointAnnotation<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> {
+    #[serde(flatten)]
+    anchor: Anchor<X, Y>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    radius: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    point_style: Option<PointStyle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_width: Option<f32>
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> PointAnnotation<X, Y> {
+
+    pub fn new(x: X, y: Y) -> Self {
+        Self {
+            anchor: Anchor { x: x.into(), y: y.into() },
+            radius: None,
+            point_style: None,
+            background_color: None,
+            border_color: None,
+            border_width: None
+        }
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    pub fn with_point_style(mut self, style: PointStyle) -> Self {
+        self.point_style = Some(style);
+        self
+    }
+
+    pub fn with_background_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.background_color = Some(color.into());
+        self
+    }
+
+    pub fn with_border_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.border_color = Some(color.into());
+        self
+    }
+
+    pub fn with_border_width(mut self, width: f32) -> Self {
+        self.border_width = Some(width);
+        self
+    }
+}
+
+/// Free-standing text at a point
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelAnnotation<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> {
+    #[serde(flatten)]
+    anchor: Anchor<X, Y>,
+    ///one entry per line
+    content: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font: Option<Font>
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> LabelAnnotation<X, Y> {
+
+    pub fn new(x: X, y: Y, text: &str) -> Self {
+        Self {
+            anchor: Anchor { x: x.into(), y: y.into() },
+            content: text.lines().map(str::to_string).collect(),
+            color: None,
+            background_color: None,
+            font: None
+        }
+    }
+
+    pub fn with_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn with_background_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.background_color = Some(color.into());
+        self
+    }
+
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::js::to_js;
+    use crate::serde::{SerializeSettings, SettingsGuard};
+
+    #[test]
+    fn annotation_test() {
+        let annotations = Annotations::<&str, f64>::default()
+            .with_annotation("slo", LineAnnotation::horizontal(300.0)
+                .with_border_dash(vec![6.0, 4.0])
+                .with_label(AnnotationLabel::new("p99 < 300 ms").with_position(LabelPosition::End)))
+            .with_annotation("window", Annotation::ellipse(BoxAnnotation::x_range("Mon", "Tue")));
+        assert_eq!(
+            to_js(&annotations).unwrap(),
+            r#"{"annotations":{"slo":{"type":"line","yMin":300.0,"yMax":300.0,"borderDash":[6.0,4.0],"label":{"display":true,"content":["p99 < 300 ms"],"position":"end"}},"window":{"type":"ellipse","xMin":"Mon","xMax":"Tue"}}}"#
+        );
+        let _guard = SettingsGuard::set(SerializeSettings{ transposed: true, ..SerializeSettings::default() });
+        assert_eq!(
+            to_js(&PointAnnotation::new("Mon", 2.0)).unwrap(),
+            r#"{"yValue":"Mon","xValue":2.0}"#
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{impl_scale_type, ScaleType};
-use chrono::{DateTime, TimeZone, Weekday};
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Weekday};
 use serde::{Serialize, Serializer};
 use crate::serde::{SerializeFormat, WithTypeAndSerializer};
+use crate::time_zone::naive_zone;
 
 impl_scale_type!(Category for chrono::Weekday chrono::Month);
 impl_scale_type!(Time for chrono::NaiveDateTime);
@@ -24,12 +25,25 @@ impl<T:TimeZone> SerializeFormat<DateTime<T>> for DateTime<T>{
 }
 
 
+/// Offset as used in RFC 3339, like +05:30
+fn rfc3339_offset(offset: i32) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let minutes = offset.unsigned_abs() / 60;
+    format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+}
+
+/// Naive date-time is put in the chart's naive time zone, see `ChartConfig::with_naive_time_zone`
 impl SerializeFormat<chrono::NaiveDateTime> for chrono::NaiveDateTime{
     fn serialize<S>(value: &chrono::NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer
     {
-        value.and_utc().to_rfc3339().serialize(serializer)
+        let offset = naive_zone().offset_of_local(
+            (value.year(), value.month(), value.day()),
+            (value.hour(), value.minute(), value.second())
+        );
+        format!("{}{}", value.format("%Y-%m-%dT%H:%M:%S%.f"), rfc3339_offset(offset))
+            .serialize(serializer)
     }
 }
 
@@ -49,4 +63,63 @@ impl SerializeFormat<Weekday> for Weekday{
     {
         value.to_string().serialize(serializer)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use sailfish::TemplateSimple;
+    use crate::common::Size;
+    use crate::time_zone::ChartTimeZone;
+    use crate::{ChartConfig, ChartType, ScaleConfig, ScaleType, TimeScaleOptions, TimeUnit};
+
+    #[test]
+    fn naive_time_zone_test() {
+        let at = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap().and_hms_opt(10, 0, 0).unwrap();
+        let html = ChartConfig::<chrono::NaiveDateTime, f64>::default()
+            .with_time_zone(ChartTimeZone::fixed_hours(2))
+            .with_naive_time_zone(ChartTimeZone::fixed_hours(-5))
+            .add_series(ChartType::Line, "set".to_string(), vec![(at, 1.0)])
+            .build(Size::pixels(600), Size::pixels(400))
+            .render_once()
+            .unwrap();
+        assert!(html.contains(r#""x":"2025-01-01T10:00:00-05:00""#));
+        assert!(html.contains(r#""adapters":{"date":{"zone":"UTC+2:00"}}"#));
+
+        //without a naive zone the display zone is used, without any zone UTC, never the browser's zone
+        let render = |config: ChartConfig<chrono::NaiveDateTime, f64>| config
+            .add_series(ChartType::Line, "set".to_string(), vec![(at, 1.0)])
+            .build(Size::pixels(600), Size::pixels(400))
+            .render_once()
+            .unwrap();
+        assert!(render(ChartConfig::default().with_time_zone(ChartTimeZone::fixed_hours(2))).contains(r#""x":"2025-01-01T10:00:00+02:00""#));
+        assert!(render(ChartConfig::default()).contains(r#""x":"2025-01-01T10:00:00+00:00""#));
+    }
+
+    #[test]
+    fn time_zone_format_test() {
+        let config = |format: &str| ChartConfig::<chrono::NaiveDateTime, f64>::default()
+            .with_time_zone(ChartTimeZone::fixed_hours(2))
+            .set_x_axis(ScaleConfig::default()
+                .with_type(ScaleType::Time)
+                .with_time(TimeScaleOptions::default()
+                    .with_display_format(TimeUnit::Day, format)
+                    .with_tooltip_format("PPpp")))
+            .build(Size::pixels(600), Size::pixels(400));
+        //date-fns formats are translated for the luxon adapter
+        assert!(config("EEE, MMM do").render_once().is_err());
+        let chart = config("EEE, MMM d 'Q'Q");
+        assert_eq!(chart.dependencies().scripts(), [crate::render::LUXON_SCRIPT.into(), crate::render::LUXON_ADAPTER_SCRIPT.into()]);
+        let html = chart.render_once().unwrap();
+        assert!(html.contains(r#""time":{"displayFormats":{"day":"EEE, MMM d 'Q'q"},"tooltipFormat":"ff"}"#));
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn iana_time_zone_test() {
+        let zone = ChartTimeZone::from(chrono_tz::Europe::Berlin);
+        assert_eq!(zone.offset_of_local((2025, 1, 1), (10, 0, 0)), 3600);
+        assert_eq!(zone.offset_of_local((2025, 7, 1), (10, 0, 0)), 7200);
+        assert_eq!(zone.luxon_zone(), "Europe/Berlin");
+    }
+}
@@ -1,9 +1,11 @@
 use std::fmt;
 use std::fmt::Write;
+use std::str::FromStr;
 use sailfish::RenderError;
 use sailfish::runtime::{Buffer, Render};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde::de::Visitor;
+use crate::js::{js_literal, serialize_raw};
 
 /// Newtype for percentage values, serialized as "{value}%"
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -68,13 +70,16 @@ impl Render for Size{
     }
 }
 
-impl Size{
-    pub fn to_string(&self) -> String{
+impl fmt::Display for Size{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self{
-            Size::Percent(p) => format!("{}%", p.0),
-            Size::Pixel(p) => format!("{}px", p.0)
+            Size::Percent(p) => write!(f, "{}%", p.0),
+            Size::Pixel(p) => write!(f, "{}px", p.0)
         }
     }
+}
+
+impl Size{
 
     pub fn percent(f: f32) -> Self{
         Size::Percent(Percent(f))
@@ -140,6 +145,419 @@ impl<'de> Deserialize<'de> for Rgb {
     }
 }
 
+impl Rgb {
+    pub fn with_alpha(&self, alpha: f32) -> Color {
+        Color::Rgba(self.0, self.1, self.2, alpha)
+    }
+}
+
+/// Any CSS color Chart.js understands, serialized in CSS form
+#[derive(Debug, Clone, PartialEq)]
+pub enum Color {
+    ///alpha goes from 0 (transparent) to 1, shown as `rgb(...)` when it is 1
+    Rgba(u8, u8, u8, f32),
+    ///`#rrggbb`, or `#rrggbbaa` when alpha is not 0xff
+    Hex(u8, u8, u8, u8),
+    ///hue in degrees, saturation and lightness in percent
+    Hsl(f32, f32, f32),
+    Hsla(f32, f32, f32, f32),
+    ///CSS color name, like "steelblue", see `Color::named`
+    Named(&'static str)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorParseError(String);
+
+impl fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid color \"{}\"", self.0)
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
+/// CSS named colors, sorted by name
+const NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xf0f8ff), ("antiquewhite", 0xfaebd7), ("aqua", 0x00ffff), ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff), ("beige", 0xf5f5dc), ("bisque", 0xffe4c4), ("black", 0x000000), ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff), ("blueviolet", 0x8a2be2), ("brown", 0xa52a2a), ("burlywood", 0xdeb887), ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00), ("chocolate", 0xd2691e), ("coral", 0xff7f50), ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc), ("crimson", 0xdc143c), ("cyan", 0x00ffff), ("darkblue", 0x00008b), ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b), ("darkgray", 0xa9a9a9), ("darkgreen", 0x006400), ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b), ("darkmagenta", 0x8b008b), ("darkolivegreen", 0x556b2f), ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc), ("darkred", 0x8b0000), ("darksalmon", 0xe9967a), ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b), ("darkslategray", 0x2f4f4f), ("darkslategrey", 0x2f4f4f), ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3), ("deeppink", 0xff1493), ("deepskyblue", 0x00bfff), ("dimgray", 0x696969),
+    ("dimgrey", 0x696969), ("dodgerblue", 0x1e90ff), ("firebrick", 0xb22222), ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22), ("fuchsia", 0xff00ff), ("gainsboro", 0xdcdcdc), ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700), ("goldenrod", 0xdaa520), ("gray", 0x808080), ("green", 0x008000), ("greenyellow", 0xadff2f),
+    ("grey", 0x808080), ("honeydew", 0xf0fff0), ("hotpink", 0xff69b4), ("indianred", 0xcd5c5c), ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0), ("khaki", 0xf0e68c), ("lavender", 0xe6e6fa), ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00), ("lemonchiffon", 0xfffacd), ("lightblue", 0xadd8e6), ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff), ("lightgoldenrodyellow", 0xfafad2), ("lightgray", 0xd3d3d3), ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3), ("lightpink", 0xffb6c1), ("lightsalmon", 0xffa07a), ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa), ("lightslategray", 0x778899), ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de), ("lightyellow", 0xffffe0), ("lime", 0x00ff00), ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6), ("magenta", 0xff00ff), ("maroon", 0x800000), ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd), ("mediumorchid", 0xba55d3), ("mediumpurple", 0x9370db), ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee), ("mediumspringgreen", 0x00fa9a), ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585), ("midnightblue", 0x191970), ("mintcream", 0xf5fffa), ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5), ("navajowhite", 0xffdead), ("navy", 0x000080), ("oldlace", 0xfdf5e6), ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23), ("orange", 0xffa500), ("orangered", 0xff4500), ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa), ("palegreen", 0x98fb98), ("paleturquoise", 0xafeeee), ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5), ("peachpuff", 0xffdab9), ("peru", 0xcd853f), ("pink", 0xffc0cb), ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6), ("purple", 0x800080), ("rebeccapurple", 0x663399), ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f), ("royalblue", 0x4169e1), ("saddlebrown", 0x8b4513), ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460), ("seagreen", 0x2e8b57), ("seashell", 0xfff5ee), ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0), ("skyblue", 0x87ceeb), ("slateblue", 0x6a5acd), ("slategray", 0x708090),
+    ("slategrey", 0x708090), ("snow", 0xfffafa), ("springgreen", 0x00ff7f), ("steelblue", 0x4682b4), ("tan", 0xd2b48c),
+    ("teal", 0x008080), ("thistle", 0xd8bfd8), ("tomato", 0xff6347), ("turquoise", 0x40e0d0), ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3), ("white", 0xffffff), ("whitesmoke", 0xf5f5f5), ("yellow", 0xffff00), ("yellowgreen", 0x9acd32),
+];
+
+impl Color {
+
+    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color::Rgba(r, g, b, 1.0)
+    }
+
+    pub fn hex(hex: &str) -> Result<Self, ColorParseError> {
+        let err = || ColorParseError(hex.to_string());
+        let digits = hex.strip_prefix('#').ok_or_else(err)?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(err());
+        }
+        let channel = |i: usize, len: usize| {
+            let value = u8::from_str_radix(&digits[i * len..(i + 1) * len], 16).unwrap_or(0);
+            if len == 1 { value * 17 } else { value }
+        };
+        match digits.len() {
+            3 => Ok(Color::Hex(channel(0, 1), channel(1, 1), channel(2, 1), 0xff)),
+            4 => Ok(Color::Hex(channel(0, 1), channel(1, 1), channel(2, 1), channel(3, 1))),
+            6 => Ok(Color::Hex(channel(0, 2), channel(1, 2), channel(2, 2), 0xff)),
+            8 => Ok(Color::Hex(channel(0, 2), channel(1, 2), channel(2, 2), channel(3, 2))),
+            _ => Err(err())
+        }
+    }
+
+    /// CSS named color, case-insensitive
+    pub fn named(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        if name == "transparent" {
+            return Some(Color::Named("transparent"));
+        }
+        NAMED_COLORS.binary_search_by(|(n, _)| (*n).cmp(name.as_str()))
+            .ok()
+            .map(|i| Color::Named(NAMED_COLORS[i].0))
+    }
+
+    /// Red, green, blue and alpha (0 to 1), unknown names are black
+    pub fn to_rgba(&self) -> (u8, u8, u8, f32) {
+        match self {
+            Color::Rgba(r, g, b, a) => (*r, *g, *b, *a),
+            Color::Hex(r, g, b, a) => (*r, *g, *b, *a as f32 / 255.0),
+            Color::Hsl(h, s, l) => hsl_to_rgba(*h, *s, *l, 1.0),
+            Color::Hsla(h, s, l, a) => hsl_to_rgba(*h, *s, *l, *a),
+            Color::Named("transparent") => (0, 0, 0, 0.0),
+            Color::Named(name) => {
+                let rgb = NAMED_COLORS.iter().find(|(n, _)| n == name).map_or(0, |(_, rgb)| *rgb);
+                ((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8, 1.0)
+            }
+        }
+    }
+
+    fn to_hsla(&self) -> (f32, f32, f32, f32) {
+        match self {
+            Color::Hsl(h, s, l) => (*h, *s, *l, 1.0),
+            Color::Hsla(h, s, l, a) => (*h, *s, *l, *a),
+            _ => {
+                let (r, g, b, a) = self.to_rgba();
+                let (h, s, l) = rgb_to_hsl(r, g, b);
+                (h, s, l, a)
+            }
+        }
+    }
+
+    /// Same color with the given alpha, from 0 (transparent) to 1
+    pub fn with_alpha(self, alpha: f32) -> Self {
+        let alpha = alpha.clamp(0.0, 1.0);
+        match self {
+            Color::Hsl(h, s, l) | Color::Hsla(h, s, l, _) => Color::Hsla(h, s, l, alpha),
+            Color::Hex(r, g, b, _) => Color::Hex(r, g, b, (alpha * 255.0).round() as u8),
+            other => {
+                let (r, g, b, _) = other.to_rgba();
+                Color::Rgba(r, g, b, alpha)
+            }
+        }
+    }
+
+    /// Raises lightness by `amount`, from 0 to 1 where 1 is 100 percentage points
+    pub fn lighten(self, amount: f32) -> Self {
+        let (h, s, l, a) = self.to_hsla();
+        Color::Hsla(h, s, (l + amount * 100.0).clamp(0.0, 100.0), a)
+    }
+
+    /// Lowers lightness by `amount`, from 0 to 1 where 1 is 100 percentage points
+    pub fn darken(self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Blend with `other`, `weight` is the share of `other`, from 0 to 1
+    pub fn mix(self, other: &Color, weight: f32) -> Self {
+        let weight = weight.clamp(0.0, 1.0);
+        let (r1, g1, b1, a1) = self.to_rgba();
+        let (r2, g2, b2, a2) = other.to_rgba();
+        let blend = |c1: u8, c2: u8| (c1 as f32 + (c2 as f32 - c1 as f32) * weight).round() as u8;
+        Color::Rgba(blend(r1, r2), blend(g1, g2), blend(b1, b2), a1 + (a2 - a1) * weight)
+    }
+}
+
+fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+    let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d == 0.0 {
+        return (0.0, 0.0, l * 100.0);
+    }
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        ((g - b) / d).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    (h * 60.0, s * 100.0, l * 100.0)
+}
+
+fn hsl_to_rgba(h: f32, s: f32, l: f32, a: f32) -> (u8, u8, u8, f32) {
+    let (s, l) = (s.clamp(0.0, 100.0) / 100.0, l.clamp(0.0, 100.0) / 100.0);
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x)
+    };
+    let m = l - c / 2.0;
+    let channel = |v: f32| ((v + m) * 255.0).round() as u8;
+    (channel(r), channel(g), channel(b), a)
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Rgba(r, g, b, a) if *a >= 1.0 => write!(f, "rgb({}, {}, {})", r, g, b),
+            Color::Rgba(r, g, b, a) => write!(f, "rgba({}, {}, {}, {})", r, g, b, a),
+            Color::Hex(r, g, b, 0xff) => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+            Color::Hex(r, g, b, a) => write!(f, "#{:02x}{:02x}{:02x}{:02x}", r, g, b, a),
+            Color::Hsl(h, s, l) => write!(f, "hsl({}, {}%, {}%)", h, s, l),
+            Color::Hsla(h, s, l, a) => write!(f, "hsla({}, {}%, {}%, {})", h, s, l, a),
+            Color::Named(name) => f.write_str(name)
+        }
+    }
+}
+
+/// Splits the arguments of a CSS function like `rgb(1, 2, 3)`
+fn css_args<'a>(value: &'a str, name: &str) -> Option<Vec<&'a str>> {
+    let args = value.strip_prefix(name)?.trim_start().strip_prefix('(')?.strip_suffix(')')?;
+    Some(args.split(',').map(str::trim).collect())
+}
+
+/// Number of a CSS color argument and whether it was a percentage, only finite numbers are accepted
+fn css_number(arg: &str) -> Option<(f32, bool)> {
+    let (number, percent) = match arg.strip_suffix('%') {
+        Some(number) => (number.trim_end(), true),
+        None => (arg, false)
+    };
+    number.parse::<f32>().ok().filter(|v| v.is_finite()).map(|v| (v, percent))
+}
+
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let err = || ColorParseError(s.to_string());
+        let parse = |args: Vec<&str>, len: usize| -> Result<Vec<(f32, bool)>, ColorParseError> {
+            if args.len() != len {
+                return Err(err());
+            }
+            args.iter().map(|a| css_number(a).ok_or_else(err)).collect()
+        };
+        //channels go from 0 to 255 or 0% to 100%
+        let byte = |(v, percent): (f32, bool)| match percent {
+            false if (0.0..=255.0).contains(&v) => Ok(v as u8),
+            true if (0.0..=100.0).contains(&v) => Ok((v * 2.55).round() as u8),
+            _ => Err(err())
+        };
+        //alpha goes from 0 to 1 or 0% to 100%
+        let alpha = |(v, percent): (f32, bool)| match percent {
+            false if (0.0..=1.0).contains(&v) => Ok(v),
+            true if (0.0..=100.0).contains(&v) => Ok(v / 100.0),
+            _ => Err(err())
+        };
+        let hue = |(v, percent): (f32, bool)| if !percent && (0.0..=360.0).contains(&v) { Ok(v) } else { Err(err()) };
+        let percentage = |(v, _): (f32, bool)| if (0.0..=100.0).contains(&v) { Ok(v) } else { Err(err()) };
+        if value.starts_with('#') {
+            Color::hex(value)
+        } else if let Some(args) = css_args(value, "rgba") {
+            let v = parse(args, 4)?;
+            Ok(Color::Rgba(byte(v[0])?, byte(v[1])?, byte(v[2])?, alpha(v[3])?))
+        } else if let Some(args) = css_args(value, "rgb") {
+            let v = parse(args, 3)?;
+            Ok(Color::Rgba(byte(v[0])?, byte(v[1])?, byte(v[2])?, 1.0))
+        } else if let Some(args) = css_args(value, "hsla") {
+            let v = parse(args, 4)?;
+            Ok(Color::Hsla(hue(v[0])?, percentage(v[1])?, percentage(v[2])?, alpha(v[3])?))
+        } else if let Some(args) = css_args(value, "hsl") {
+            let v = parse(args, 3)?;
+            Ok(Color::Hsl(hue(v[0])?, percentage(v[1])?, percentage(v[2])?))
+        } else {
+            Color::named(value).ok_or_else(err)
+        }
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D>(deserializer: D) -> Result<Color, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+impl From<Rgb> for Color {
+    fn from(value: Rgb) -> Self {
+        Color::rgb(value.0, value.1, value.2)
+    }
+}
+
+/// Where a gradient goes, relative to the chart area
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientDirection {
+    BottomToTop,
+    TopToBottom,
+    LeftToRight,
+    RightToLeft,
+    ///from the center of the chart area outwards
+    Radial
+}
+
+/// Gradient built on the client side from the chart area, as a scriptable option
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    direction: GradientDirection,
+    stops: Vec<(f32, Color)>
+}
+
+impl Gradient {
+
+    pub fn new(direction: GradientDirection) -> Self {
+        Self {
+            direction,
+            stops: vec![]
+        }
+    }
+
+    ///`offset` goes from 0 (start of the gradient) to 1 (end)
+    pub fn with_stop<C: Into<Color>>(mut self, offset: f32, color: C) -> Self {
+        self.stops.push((offset.clamp(0.0, 1.0), color.into()));
+        self
+    }
+
+    /// JS function that returns the `CanvasGradient`, `null` before the first layout
+    pub(crate) fn js_function(&self) -> String {
+        let create = match self.direction {
+            GradientDirection::BottomToTop => "ctx.createLinearGradient(0, a.bottom, 0, a.top)",
+            GradientDirection::TopToBottom => "ctx.createLinearGradient(0, a.top, 0, a.bottom)",
+            GradientDirection::LeftToRight => "ctx.createLinearGradient(a.left, 0, a.right, 0)",
+            GradientDirection::RightToLeft => "ctx.createLinearGradient(a.right, 0, a.left, 0)",
+            GradientDirection::Radial => "ctx.createRadialGradient(\
+                (a.left + a.right) / 2, (a.top + a.bottom) / 2, 0, \
+                (a.left + a.right) / 2, (a.top + a.bottom) / 2, Math.max(a.right - a.left, a.bottom - a.top) / 2)"
+        };
+        let stops: String = self.stops.iter()
+            .map(|(offset, color)| format!(" g.addColorStop({}, {});", offset, js_literal(color)))
+            .collect();
+        format!(
+            "function(context){{ const a = context.chart.chartArea; if (!a) {{ return null; }} \
+             const ctx = context.chart.ctx; const g = {create};{stops} return g; }}"
+        )
+    }
+}
+
+/// What a background or border is painted with, taken by dataset and element styles,
+/// the tooltip background, scale borders and fills between datasets
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    Color(Color),
+    Gradient(Gradient),
+    ///one color per data point, in data order
+    PerPoint(Vec<Color>)
+}
+
+impl<C: Into<Color>> From<C> for Paint {
+    fn from(value: C) -> Self {
+        Paint::Color(value.into())
+    }
+}
+
+impl From<Gradient> for Paint {
+    fn from(value: Gradient) -> Self {
+        Paint::Gradient(value)
+    }
+}
+
+/// Only colors can be read back, gradients are JS
+impl<'de> Deserialize<'de> for Paint {
+    fn deserialize<D>(deserializer: D) -> Result<Paint, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Colors {
+            One(Color),
+            PerPoint(Vec<Color>)
+        }
+        Ok(match Colors::deserialize(deserializer)? {
+            Colors::One(color) => Paint::Color(color),
+            Colors::PerPoint(colors) => Paint::PerPoint(colors)
+        })
+    }
+}
+
+impl Serialize for Paint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Paint::Color(color) => color.serialize(serializer),
+            Paint::Gradient(gradient) => serialize_raw(&gradient.js_function(), serializer),
+            Paint::PerPoint(colors) => colors.serialize(serializer)
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Padding {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -150,4 +568,92 @@ pub struct Padding {
     pub left: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub right: Option<f32>
-}
\ No newline at end of file
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum FontStyle {
+    Normal,
+    Italic,
+    Oblique
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Font {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    family: Option<String>,
+
+    ///font size in px
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<f32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    style: Option<FontStyle>,
+
+    ///css font weight, 400 is normal and 700 is bold
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<u16>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line_height: Option<f32>
+}
+
+impl Font {
+
+    pub fn with_family(mut self, family: &str) -> Self {
+        self.family = Some(family.to_string());
+        self
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn with_style(mut self, style: FontStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn with_weight(mut self, weight: u16) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    pub fn with_line_height(mut self, line_height: f32) -> Self {
+        self.line_height = Some(line_height);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::js::to_js;
+    use crate::options::{BorderConfig, Boundary, DatasetStyle, Fill, FillVariant, Tooltip};
+
+    #[test]
+    fn gradient_test() {
+        let style = DatasetStyle::default()
+            .with_background_color(Gradient::new(GradientDirection::BottomToTop)
+                .with_stop(0.0, Color::rgb(0, 0, 255).with_alpha(0.0))
+                .with_stop(1.0, Color::named("steelblue").unwrap()));
+        assert_eq!(
+            to_js(&style).unwrap(),
+            r#"{"backgroundColor":function(context){ const a = context.chart.chartArea; if (!a) { return null; } const ctx = context.chart.ctx; const g = ctx.createLinearGradient(0, a.bottom, 0, a.top); g.addColorStop(0, "rgba(0, 0, 255, 0)"); g.addColorStop(1, "steelblue"); return g; }}"#
+        );
+
+        //fills and scale borders take gradients too, the tooltip background as well
+        let gradient = Gradient::new(GradientDirection::LeftToRight).with_stop(0.0, Color::named("red").unwrap());
+        let gradient_js = r#"function(context){ const a = context.chart.chartArea; if (!a) { return null; } const ctx = context.chart.ctx; const g = ctx.createLinearGradient(a.left, 0, a.right, 0); g.addColorStop(0, "red"); return g; }"#;
+        let fill = Fill::new(FillVariant::Boundary(Boundary::Origin)).with_above(gradient.clone()).with_below(Color::named("blue").unwrap());
+        assert_eq!(
+            to_js(&fill).unwrap(),
+            format!(r#"function(context){{ const f = {{"target":"origin","above":{},"below":"blue"}}; if (typeof f.above === 'function') {{ f.above = f.above(context); }} if (typeof f.below === 'function') {{ f.below = f.below(context); }} return f; }}"#, gradient_js)
+        );
+        assert_eq!(to_js(&Fill::new(FillVariant::AbsIndex(1)).with_below(Color::named("blue").unwrap())).unwrap(), r#"{"target":1,"below":"blue"}"#);
+        assert_eq!(to_js(&BorderConfig::default().with_color(gradient.clone())).unwrap(), format!(r#"{{"color":{}}}"#, gradient_js));
+        assert!(to_js(&Tooltip::default().with_background_color(gradient)).unwrap().contains(&format!(r#""backgroundColor":{}"#, gradient_js)));
+    }
+}
@@ -1,7 +1,10 @@
-use serde::ser::SerializeSeq;
+use sailfish::{RenderError, TemplateSimple};
+use sailfish::runtime::escape::escape_to_string;
 use serde::{Deserialize, Serialize};
-use crate::data::ChartData::{Vector2D, VectorWithRadius, VectorWithText};
-use crate::serde::{ValueSerializeWrapper, WithTypeAndSerializer};
+use serde_json::{Map, Value};
+use crate::data::ChartData::{Vector2D, VectorWithMeta, VectorWithRadius, VectorWithText};
+use crate::NonFinitePolicy;
+use crate::serde::{settings, ValueSerializeWrapper, WithTypeAndSerializer};
 
 impl<X,Y> From<(X, Y, String)> for DataPointWithTooltip<X,Y>{
     fn from(value: (X, Y, String)) -> Self {
@@ -28,7 +31,8 @@ impl<X:WithTypeAndSerializer,Y:WithTypeAndSerializer> From<ChartData<X,Y>> for V
         match value {
             Vector2D(v) => v.into_iter().map(|v| (v.0.0,v.1.0)).collect(),
             VectorWithRadius(val)=> val.into_iter().map(|v| (v.x.0,v.y.0)).collect(),
-            VectorWithText(val)=> val.into_iter().map(|v|  (v.x.0,v.y.0)).collect()
+            VectorWithText(val)=> val.into_iter().map(|v|  (v.x.0,v.y.0)).collect(),
+            VectorWithMeta(val)=> val.into_iter().map(|v|  (v.x.0,v.y.0)).collect()
         }
     }
 }
@@ -58,6 +62,17 @@ impl<const N: usize,X,Y> From<[(X,Y,&str);N]> for ChartData<X,Y> where X:WithTyp
 }
 
 
+impl<X,Y> From<Vec<(X,Y,PointMeta)>> for ChartData<X,Y> where X:WithTypeAndSerializer, Y:WithTypeAndSerializer{
+    fn from(value: Vec<(X, Y, PointMeta)>) -> Self {
+        VectorWithMeta(value.into_iter().map(|v| (v.0.into(),v.1.into(),v.2).into()).collect())
+    }
+}
+
+impl<const N: usize,X,Y> From<[(X,Y,PointMeta);N]> for ChartData<X,Y> where X:WithTypeAndSerializer, Y:WithTypeAndSerializer{
+    fn from(value: [(X,Y,PointMeta);N]) -> Self {
+        VectorWithMeta(value.into_iter().map(|v| (v.0.into(),v.1.into(),v.2).into()).collect())
+    }
+}
 
 impl<X,Y> From<Vec<(X,Y)>> for ChartData<X,Y> where X:WithTypeAndSerializer, Y:WithTypeAndSerializer{
     fn from(value: Vec<(X, Y)>) -> Self {
@@ -75,23 +90,34 @@ impl<const N: usize,X,Y> From<[(X,Y);N]> for ChartData<X,Y> where X:WithTypeAndS
 pub enum ChartData<X,Y> where X:WithTypeAndSerializer, Y:WithTypeAndSerializer{
     Vector2D(Vec<(ValueSerializeWrapper<X>,ValueSerializeWrapper<Y>)>),
     VectorWithRadius(Vec<DataPointWithRadius<ValueSerializeWrapper<X>,ValueSerializeWrapper<Y>>>),
-    VectorWithText(Vec<DataPointWithTooltip<ValueSerializeWrapper<X>,ValueSerializeWrapper<Y>>>)
+    VectorWithText(Vec<DataPointWithTooltip<ValueSerializeWrapper<X>,ValueSerializeWrapper<Y>>>),
+    VectorWithMeta(Vec<DataPointWithMeta<ValueSerializeWrapper<X>,ValueSerializeWrapper<Y>>>)
 }
 
 
+impl<X,Y> ChartData<X,Y> where X:WithTypeAndSerializer, Y:WithTypeAndSerializer{
+
+    /// Whether each point has finite values, points that don't are left out with `NonFinitePolicy::Skip`
+    pub(crate) fn finite_points(&self) -> Vec<bool> {
+        let finite = |x: &ValueSerializeWrapper<X>, y: &ValueSerializeWrapper<Y>| x.0.is_finite() && y.0.is_finite();
+        match self {
+            Vector2D(v) => v.iter().map(|(x,y)| finite(x,y)).collect(),
+            VectorWithRadius(v) => v.iter().map(|p| finite(&p.x,&p.y)).collect(),
+            VectorWithText(v) => v.iter().map(|p| finite(&p.x,&p.y)).collect(),
+            VectorWithMeta(v) => v.iter().map(|p| finite(&p.x,&p.y)).collect()
+        }
+    }
+}
+
 impl<X,Y> Serialize for ChartData<X,Y> where X:WithTypeAndSerializer, Y:WithTypeAndSerializer{
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        let skip = settings().non_finite == NonFinitePolicy::Skip;
+        let keep = |x: &ValueSerializeWrapper<X>, y: &ValueSerializeWrapper<Y>| !skip || (x.0.is_finite() && y.0.is_finite());
         match self {
-            Vector2D(v) => {
-                let mut v_ser = serializer.serialize_seq(Some(v.len()))?;
-                for (x,y) in v {
-                    let  point = DataPoint{x,y};
-                    v_ser.serialize_element(&point)?;
-                }
-                v_ser.end()
-            },
-            VectorWithRadius(v) => v.serialize(serializer),
-            VectorWithText(v) => v.serialize(serializer)
+            Vector2D(v) => serializer.collect_seq(v.iter().filter(|(x,y)| keep(x,y)).map(|(x,y)| DataPoint{x,y})),
+            VectorWithRadius(v) => serializer.collect_seq(v.iter().filter(|p| keep(&p.x,&p.y))),
+            VectorWithText(v) => serializer.collect_seq(v.iter().filter(|p| keep(&p.x,&p.y))),
+            VectorWithMeta(v) => serializer.collect_seq(v.iter().filter(|p| keep(&p.x,&p.y)))
         }
     }
 }
@@ -110,6 +136,93 @@ pub struct DataPointWithTooltip<X,Y>{
     tooltip: String
 }
 
+/// Named values attached to a data point, like host or commit, shown by a `TooltipLayout`
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PointMeta {
+    #[serde(flatten)]
+    fields: Map<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tooltip_html: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>
+}
+
+impl PointMeta {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///fields of a struct or a map, anything serializing to something else is an error
+    pub fn from_serialize<T: Serialize + ?Sized>(value: &T) -> Result<Self, serde_json::Error> {
+        match serde_json::to_value(value)? {
+            Value::Object(fields) => Ok(PointMeta { fields, ..PointMeta::default() }),
+            other => Err(serde::ser::Error::custom(format!("point metadata has to be an object, got {}", other)))
+        }
+    }
+
+    pub fn with<T: Serialize + ?Sized>(mut self, key: &str, value: &T) -> Result<Self, serde_json::Error> {
+        self.fields.insert(key.to_string(), serde_json::to_value(value)?);
+        Ok(self)
+    }
+
+    ///HTML shown by an `ExternalTooltip` for the point, rendered on the server from a sailfish template,
+    ///which escapes the values it outputs with `<%= %>`
+    pub fn with_tooltip_template<T: TemplateSimple>(mut self, template: T) -> Result<Self, RenderError> {
+        self.tooltip_html = Some(template.render_once()?);
+        Ok(self)
+    }
+
+    ///text shown by an `ExternalTooltip` for the point, escaped so markup in it shows as it is
+    pub fn with_tooltip_html(mut self, text: &str) -> Self {
+        let mut html = String::with_capacity(text.len());
+        escape_to_string(text, &mut html);
+        self.tooltip_html = Some(html);
+        self
+    }
+
+    ///HTML shown by an `ExternalTooltip` for the point as it is, it goes into `innerHTML` without sanitizing,
+    ///so values from users have to be escaped by the caller, `with_tooltip_template` does that
+    pub fn with_tooltip_html_unescaped<S: Into<String>>(mut self, html: S) -> Self {
+        self.tooltip_html = Some(html.into());
+        self
+    }
+
+    ///link followed when the point is clicked, see `ClickConfig`
+    pub fn with_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.fields.get(key)
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DataPointWithMeta<X,Y>{
+    x: X,
+    y: Y,
+    meta: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tooltip_html: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>
+}
+
+impl<X,Y> From<(X, Y, PointMeta)> for DataPointWithMeta<X,Y>{
+    fn from(value: (X, Y, PointMeta)) -> Self {
+        DataPointWithMeta{
+            x: value.0,
+            y: value.1,
+            meta: value.2.fields,
+            tooltip_html: value.2.tooltip_html,
+            url: value.2.url
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DataPointWithRadius<X,Y>{
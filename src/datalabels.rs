@@ -0,0 +1,267 @@
+use std::borrow::Cow;
+use serde::{Serialize, Serializer};
+use crate::common::{Color, Font, Padding};
+use crate::format::NumberFormat;
+use crate::js::serialize_raw;
+use crate::options::JsExpr;
+use crate::plugin::{ChartPlugin, ScriptSource};
+
+/// chartjs-plugin-datalabels, it is enabled only for the charts that have data labels
+pub const DATALABELS_SCRIPT: &str = "https://cdn.jsdelivr.net/npm/chartjs-plugin-datalabels@2";
+
+/// chartjs-plugin-datalabels, enabled per chart since registering it would label every chart on the page
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DataLabelsPlugin;
+
+impl ChartPlugin for DataLabelsPlugin {
+    fn options_key(&self) -> Cow<'static, str> {
+        Cow::Borrowed("datalabels")
+    }
+
+    fn scripts(&self) -> Vec<ScriptSource> {
+        vec![DATALABELS_SCRIPT.into()]
+    }
+
+    fn local_instance(&self) -> Option<Cow<'static, str>> {
+        Some(Cow::Borrowed("ChartDataLabels"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LabelDisplay {
+    Always,
+    Never,
+    ///hidden where labels would overlap
+    Auto
+}
+
+impl Serialize for LabelDisplay {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        match self {
+            LabelDisplay::Always => serializer.serialize_bool(true),
+            LabelDisplay::Never => serializer.serialize_bool(false),
+            LabelDisplay::Auto => serializer.serialize_str("auto")
+        }
+    }
+}
+
+/// Point of the bar, slice or point the label is anchored to
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelAnchor {
+    Start,
+    Center,
+    End
+}
+
+/// Where the label goes relative to its anchor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LabelAlign {
+    Center,
+    ///away from the origin of the bar or slice
+    End,
+    ///towards the origin of the bar or slice
+    Start,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    ///angle in degrees, clockwise from the right
+    Degrees(f32)
+}
+
+impl Serialize for LabelAlign {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let align = match self {
+            LabelAlign::Center => "center",
+            LabelAlign::End => "end",
+            LabelAlign::Start => "start",
+            LabelAlign::Top => "top",
+            LabelAlign::Bottom => "bottom",
+            LabelAlign::Left => "left",
+            LabelAlign::Right => "right",
+            LabelAlign::Degrees(degrees) => return serializer.serialize_f32(*degrees)
+        };
+        serializer.serialize_str(align)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum LabelFormatter {
+    Format(NumberFormat),
+    ///custom `function(value, context)`, `value` is the whole data point
+    Custom(JsExpr)
+}
+
+impl Serialize for LabelFormatter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        match self {
+            LabelFormatter::Format(format) => {
+                //data points are {x, y} objects, the value is on the axis opposite to the index one of the dataset,
+                //read under the key its parsing gives for that axis
+                serialize_raw(
+                    &format!(
+                        "function(value, context){{ if (value !== null && typeof value === 'object') {{ const d = context.dataset; \
+                        const axis = (d.indexAxis || context.chart.options.indexAxis) === 'y' ? 'x' : 'y'; \
+                        value = value[(d.parsing && d.parsing[axis + 'AxisKey']) || axis]; }} {} }}",
+                        format.js_body("value")
+                    ),
+                    serializer
+                )
+            }
+            LabelFormatter::Custom(expr) => expr.serialize(serializer)
+        }
+    }
+}
+
+/// Value labels drawn on bars, slices and points, see `ChartConfig::with_data_labels` and `Dataset::with_data_labels`
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DataLabels {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display: Option<LabelDisplay>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    anchor: Option<LabelAnchor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    align: Option<LabelAlign>,
+    ///distance from the anchor in pixels, used for all aligns but center
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<f32>,
+    ///keeps labels inside of the chart area
+    #[serde(skip_serializing_if = "Option::is_none")]
+    clamp: Option<bool>,
+    ///rotation in degrees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rotation: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font: Option<Font>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_radius: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    padding: Option<Padding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    formatter: Option<LabelFormatter>
+}
+
+impl DataLabels {
+
+    /// Labels shown unless set otherwise, for datasets that have labels when the chart doesn't
+    pub(crate) fn shown_by_default(&mut self) {
+        self.display.get_or_insert(LabelDisplay::Always);
+    }
+
+    pub fn with_display(mut self, display: LabelDisplay) -> Self {
+        self.display = Some(display);
+        self
+    }
+
+    pub fn with_anchor(mut self, anchor: LabelAnchor) -> Self {
+        self.anchor = Some(anchor);
+        self
+    }
+
+    pub fn with_align(mut self, align: LabelAlign) -> Self {
+        self.align = Some(align);
+        self
+    }
+
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub fn with_clamp(mut self, clamp: bool) -> Self {
+        self.clamp = Some(clamp);
+        self
+    }
+
+    pub fn with_rotation(mut self, degrees: f32) -> Self {
+        self.rotation = Some(degrees);
+        self
+    }
+
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    pub fn with_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn with_background_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.background_color = Some(color.into());
+        self
+    }
+
+    pub fn with_border_radius(mut self, radius: f32) -> Self {
+        self.border_radius = Some(radius);
+        self
+    }
+
+    pub fn with_padding(mut self, padding: Padding) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    ///label text from the value, the same way as axis ticks
+    pub fn with_format(mut self, format: NumberFormat) -> Self {
+        self.formatter = Some(LabelFormatter::Format(format));
+        self
+    }
+
+    pub fn with_formatter(mut self, expr: JsExpr) -> Self {
+        self.formatter = Some(LabelFormatter::Custom(expr));
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::js::to_js;
+
+    #[test]
+    fn data_labels_test() {
+        let labels = DataLabels::default()
+            .with_anchor(LabelAnchor::End)
+            .with_align(LabelAlign::Degrees(-45.0))
+            .with_format(NumberFormat::percent());
+        let js = to_js(&labels).unwrap();
+        assert!(js.starts_with(r#"{"anchor":"end","align":-45.0,"formatter":function(value, context){ if (value !== null && typeof value === 'object') { const d = context.dataset; const axis = (d.indexAxis || context.chart.options.indexAxis) === 'y' ? 'x' : 'y'; value = value[(d.parsing && d.parsing[axis + 'AxisKey']) || axis]; } let v = Number(value);"#));
+    }
+
+    #[test]
+    fn dataset_labels_test() {
+        use sailfish::TemplateSimple;
+        use crate::common::Size;
+        use crate::{AxisName, ChartConfig, ChartType, Dataset};
+        //labels of one dataset don't show up on the others
+        let html = ChartConfig::<f64, f64>::default()
+            .add_series_direct(Dataset::new(ChartType::Bar, "labeled".to_string(), [(1.0, 2.0)]).with_data_labels(DataLabels::default().with_anchor(LabelAnchor::End)))
+            .add_series(ChartType::Bar, "bare".to_string(), [(1.0, 3.0)])
+            .build(Size::pixels(600), Size::pixels(400))
+            .render_once()
+            .unwrap();
+        assert!(html.contains(r#"datalabels: {"display":false},"#));
+        assert!(html.contains(r#""label":"labeled","data":[{"x":1.0,"y":2.0}],"datalabels":{"display":true,"anchor":"end"}}"#));
+        assert!(html.contains(r#""label":"bare","data":[{"x":1.0,"y":3.0}]}"#));
+
+        //a horizontal dataset reads its value through its own parsing, whatever the chart's index axis
+        let html = ChartConfig::<&str, f64>::default()
+            .add_series_direct(Dataset::new(ChartType::Bar, "h".to_string(), [("A", 2.0)])
+                .with_index_axis(AxisName::Y)
+                .with_data_labels(DataLabels::default().with_format(NumberFormat::number())))
+            .build(Size::pixels(600), Size::pixels(400))
+            .render_once()
+            .unwrap();
+        assert!(html.contains(r#""data":[{"x":"A","y":2.0}],"indexAxis":"y","parsing":{"xAxisKey":"y","yAxisKey":"x"},"datalabels":{"display":true,"formatter":function(value, context)"#));
+        assert!(html.contains("value = value[(d.parsing && d.parsing[axis + 'AxisKey']) || axis];"));
+    }
+}
@@ -0,0 +1,179 @@
+use bigdecimal::{BigDecimal, FromPrimitive, RoundingMode, ToPrimitive};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Serialize, Serializer};
+use serde::ser::Error;
+use serde_json::value::RawValue;
+use crate::{impl_scale_type, AsF64, ScaleType};
+use crate::serde::{settings, SerializeFormat, WithTypeAndSerializer};
+
+impl_scale_type!(Linear for Decimal BigDecimal);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rounding {
+    ///to the nearest, ties to even (banker's rounding)
+    HalfEven,
+    ///to the nearest, ties away from zero
+    HalfUp,
+    ///towards zero
+    Down,
+    ///away from zero
+    Up,
+    ///towards negative infinity
+    Floor,
+    ///towards positive infinity
+    Ceiling
+}
+
+/// How decimal values of a chart are rounded before they are serialized
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecimalRounding {
+    pub places: u32,
+    pub mode: Rounding
+}
+
+impl DecimalRounding {
+    pub fn new(places: u32, mode: Rounding) -> Self {
+        Self { places, mode }
+    }
+
+    fn round_decimal(&self, value: &Decimal) -> Decimal {
+        let strategy = match self.mode {
+            Rounding::HalfEven => RoundingStrategy::MidpointNearestEven,
+            Rounding::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+            Rounding::Down => RoundingStrategy::ToZero,
+            Rounding::Up => RoundingStrategy::AwayFromZero,
+            Rounding::Floor => RoundingStrategy::ToNegativeInfinity,
+            Rounding::Ceiling => RoundingStrategy::ToPositiveInfinity
+        };
+        value.round_dp_with_strategy(self.places, strategy)
+    }
+
+    fn round_big_decimal(&self, value: &BigDecimal) -> BigDecimal {
+        let mode = match self.mode {
+            Rounding::HalfEven => RoundingMode::HalfEven,
+            Rounding::HalfUp => RoundingMode::HalfUp,
+            Rounding::Down => RoundingMode::Down,
+            Rounding::Up => RoundingMode::Up,
+            Rounding::Floor => RoundingMode::Floor,
+            Rounding::Ceiling => RoundingMode::Ceiling
+        };
+        value.with_scale_round(self.places as i64, mode).normalized()
+    }
+}
+
+fn rounding() -> Option<DecimalRounding> {
+    settings().decimal_rounding
+}
+
+/// Decimal text as a JSON number, without going through f64
+fn serialize_number<S>(text: String, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer
+{
+    RawValue::from_string(text).map_err(|err| Error::custom(err.to_string()))?.serialize(serializer)
+}
+
+
+impl SerializeFormat<Decimal> for Decimal{
+    fn serialize<S>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        let value = match rounding() {
+            Some(rounding) => rounding.round_decimal(value),
+            None => *value
+        };
+        serialize_number(value.normalize().to_string(), serializer)
+    }
+}
+
+impl SerializeFormat<BigDecimal> for BigDecimal{
+    fn serialize<S>(value: &BigDecimal, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        let value = match rounding() {
+            Some(rounding) => rounding.round_big_decimal(value),
+            None => value.normalized()
+        };
+        serialize_number(value.to_string(), serializer)
+    }
+}
+
+
+impl AsF64 for Decimal {
+    fn as_f64(&self) -> f64 {
+        self.to_f64().unwrap_or(f64::NAN)
+    }
+
+    ///rounded to the digits an f64 holds, so `2.1` stays `2.1` instead of its binary expansion
+    fn from_f64(value: f64) -> Option<Self> {
+        <Decimal as FromPrimitive>::from_f64(value)
+    }
+}
+
+impl AsF64 for BigDecimal {
+    fn as_f64(&self) -> f64 {
+        self.to_f64().unwrap_or(f64::NAN)
+    }
+
+    ///shortest decimal that reads back as the same f64, so `2.1` stays `2.1` instead of its binary expansion
+    fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        value.to_string().parse().ok()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use serde_json::to_string;
+    use super::*;
+    use crate::serde::{SerializeSettings, SettingsGuard, ValueSerializeWrapper};
+
+    #[test]
+    fn decimal_test() {
+        let price = Decimal::from_str("12345678901234.56789").unwrap();
+        assert_eq!(to_string(&ValueSerializeWrapper(price)).unwrap(), "12345678901234.56789");
+        let big = BigDecimal::from_str("0.1000000000000000000000001").unwrap();
+        assert_eq!(to_string(&ValueSerializeWrapper(big.clone())).unwrap(), "0.1000000000000000000000001");
+
+        let _guard = SettingsGuard::set(SerializeSettings{
+            decimal_rounding: Some(DecimalRounding::new(2, Rounding::HalfEven)),
+            ..SerializeSettings::default()
+        });
+        assert_eq!(to_string(&ValueSerializeWrapper(price)).unwrap(), "12345678901234.57");
+        assert_eq!(to_string(&ValueSerializeWrapper(big)).unwrap(), "0.1");
+    }
+
+    #[test]
+    fn decimal_regression_test() {
+        use crate::options::{regression_input, regression_line};
+        use crate::RegressionError;
+        assert_eq!(<Decimal as AsF64>::from_f64(f64::NAN), None);
+        assert_eq!(<Decimal as AsF64>::from_f64(1e30), None);
+        assert_eq!(<BigDecimal as AsF64>::from_f64(f64::INFINITY), None);
+
+        let data: Vec<(Decimal, Decimal)> = [("1", "2.5"), ("2", "4.5"), ("3", "6.5")].iter()
+            .map(|(x, y)| (Decimal::from_str(x).unwrap(), Decimal::from_str(y).unwrap()))
+            .collect();
+        let fit = regression_input(&data, true).unwrap();
+        assert_eq!(fit.iter().map(|(_, x, y)| (*x, *y)).collect::<Vec<_>>(), [(1.0, 2.5), (2.0, 4.5), (3.0, 6.5)]);
+        //y = 2x + 0.5
+        let line = regression_line(&fit, fit.iter().map(|(_, x, _)| 2.0 * x + 0.5), true).unwrap();
+        assert_eq!(line, data);
+
+        //fitted values Decimal can't hold are left out, or fail with NonFinitePolicy::Error
+        assert_eq!(regression_line(&fit, [2.5, f64::NAN, 1e30], false).unwrap(), data[..1]);
+        assert!(matches!(regression_line(&fit, [2.5, f64::NAN, 1e30], true), Err(RegressionError::NonFinite(_))));
+
+        //fitted values that are not exact in binary keep no precision noise
+        let line: Vec<(Decimal, Decimal)> = regression_line(&fit, [2.1, 0.1 + 0.2, 6.5], true).unwrap();
+        assert_eq!(to_string(&ValueSerializeWrapper(line[0].1)).unwrap(), "2.1");
+        assert_eq!(to_string(&ValueSerializeWrapper(line[1].1)).unwrap(), "0.3");
+        assert_eq!(to_string(&ValueSerializeWrapper(<BigDecimal as AsF64>::from_f64(2.1).unwrap())).unwrap(), "2.1");
+    }
+}
@@ -0,0 +1,179 @@
+use serde::Serialize;
+use crate::js::js_literal;
+
+/// How a number is turned into text
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumberStyle {
+    ///plain number, with locale grouping
+    Number,
+    ///value is a fraction, 0.25 is shown as 25%
+    Percent,
+    ///value in bytes, shown as kB, MB, ... (or KiB, MiB, ... when binary)
+    Bytes { binary: bool },
+    ///ISO 4217 currency code, like "USD" or "EUR"
+    Currency(String),
+    ///SI suffixes: 1500 is 1.5k, 0.002 is 2m
+    Si,
+    ///value in milliseconds, shown as "250 ms" or "1m 30s"
+    Duration
+}
+
+/// Typed number formatting, turned into a JS function on the client side.
+///
+/// All user-provided text (locale, currency, prefix, suffix) is JSON-encoded into the generated code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberFormat {
+    style: NumberStyle,
+    decimals: Option<u8>,
+    locale: Option<String>,
+    prefix: Option<String>,
+    suffix: Option<String>
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IntlOptions<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    style: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    currency: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minimum_fraction_digits: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    maximum_fraction_digits: Option<u8>
+}
+
+impl NumberFormat {
+
+    pub fn new(style: NumberStyle) -> Self {
+        Self {
+            style,
+            decimals: None,
+            locale: None,
+            prefix: None,
+            suffix: None
+        }
+    }
+
+    pub fn number() -> Self {
+        Self::new(NumberStyle::Number)
+    }
+
+    pub fn percent() -> Self {
+        Self::new(NumberStyle::Percent)
+    }
+
+    pub fn bytes() -> Self {
+        Self::new(NumberStyle::Bytes { binary: false })
+    }
+
+    pub fn binary_bytes() -> Self {
+        Self::new(NumberStyle::Bytes { binary: true })
+    }
+
+    pub fn currency(code: &str) -> Self {
+        Self::new(NumberStyle::Currency(code.to_string()))
+    }
+
+    pub fn si() -> Self {
+        Self::new(NumberStyle::Si)
+    }
+
+    pub fn duration() -> Self {
+        Self::new(NumberStyle::Duration)
+    }
+
+    ///fixed number of digits after the decimal point
+    pub fn with_decimals(mut self, decimals: u8) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    ///BCP 47 locale, like "en-US", browser locale is used when not set
+    pub fn with_locale(mut self, locale: &str) -> Self {
+        self.locale = Some(locale.to_string());
+        self
+    }
+
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.to_string());
+        self
+    }
+
+    pub fn with_suffix(mut self, suffix: &str) -> Self {
+        self.suffix = Some(suffix.to_string());
+        self
+    }
+
+    fn intl_options(&self) -> IntlOptions<'_> {
+        let (style, currency) = match &self.style {
+            NumberStyle::Percent => (Some("percent"), None),
+            NumberStyle::Currency(code) => (Some("currency"), Some(code.as_str())),
+            _ => (None, None)
+        };
+        IntlOptions {
+            style,
+            currency,
+            minimum_fraction_digits: self.decimals,
+            maximum_fraction_digits: self.decimals
+        }
+    }
+
+    /// JS statements that format the number in `var` and return the text
+    pub(crate) fn js_body(&self, var: &str) -> String {
+        let locale = self.locale.as_ref().map_or("undefined".to_string(), js_literal);
+        let options = js_literal(&self.intl_options());
+        let scaled = match &self.style {
+            NumberStyle::Bytes { binary } => {
+                let (base, units) = if *binary {
+                    (1024, r#"['B','KiB','MiB','GiB','TiB','PiB','EiB']"#)
+                } else {
+                    (1000, r#"['B','kB','MB','GB','TB','PB','EB']"#)
+                };
+                format!(
+                    "const u = {units}; let i = 0; \
+                     while (Math.abs(v) >= {base} && i < u.length - 1) {{ v /= {base}; i++; }} \
+                     let s = f.format(v) + ' ' + u[i];"
+                )
+            }
+            NumberStyle::Si => {
+                "const u = ['n','µ','m','','k','M','G','T','P','E']; let i = 3; \
+                 while (Math.abs(v) >= 1000 && i < u.length - 1) { v /= 1000; i++; } \
+                 while (v !== 0 && Math.abs(v) < 1 && i > 0) { v *= 1000; i--; } \
+                 let s = f.format(v) + u[i];".to_string()
+            }
+            NumberStyle::Duration => {
+                //two most significant units are enough for a label
+                "let s; \
+                 if (Math.abs(v) < 1000) { s = f.format(v) + ' ms'; } else { \
+                 const u = [['d', 86400000], ['h', 3600000], ['m', 60000]]; \
+                 let r = Math.abs(v); const p = []; \
+                 for (const [n, size] of u) { const c = Math.floor(r / size); if (c > 0) { p.push(c + n); r -= c * size; } } \
+                 if (r > 0 || p.length === 0) { p.push(f.format(r / 1000) + 's'); } \
+                 s = (v < 0 ? '-' : '') + p.slice(0, 2).join(' '); }".to_string()
+            }
+            _ => "let s = f.format(v);".to_string()
+        };
+        format!(
+            "let v = Number({var}); \
+             const f = new Intl.NumberFormat({locale}, {options}); \
+             {scaled} \
+             return {prefix} + s + {suffix};",
+            prefix = js_literal(self.prefix.as_deref().unwrap_or("")),
+            suffix = js_literal(self.suffix.as_deref().unwrap_or(""))
+        )
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn currency_format_test() {
+        let body = NumberFormat::currency("EUR").with_decimals(2).with_suffix("\" + alert(1) + \"").js_body("value");
+        assert!(body.contains(r#"new Intl.NumberFormat(undefined, {"style":"currency","currency":"EUR","minimumFractionDigits":2,"maximumFractionDigits":2})"#));
+        assert!(body.contains(r#"return "" + s + "\" + alert(1) + \"";"#));
+    }
+}
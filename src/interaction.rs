@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use crate::js::js_literal;
+
+/// Where a clicked link opens
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LinkTarget {
+    #[default]
+    SameTab,
+    NewTab
+}
+
+/// What clicking a data point does, see `ChartConfig::with_click`.
+///
+/// Points link to their `PointMeta::with_url`, other points to the URL of their category.
+/// Only http and https links are followed.
+#[derive(Debug, Clone, Default)]
+pub struct ClickConfig {
+    target: LinkTarget,
+    category_urls: BTreeMap<String, String>,
+    event: Option<String>
+}
+
+impl ClickConfig {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_target(mut self, target: LinkTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    ///link for every point of a category, points with their own URL keep it
+    pub fn with_category_url(mut self, category: &str, url: &str) -> Self {
+        self.category_urls.insert(category.to_string(), url.to_string());
+        self
+    }
+
+    ///`CustomEvent` dispatched on the canvas on click, before following the link.
+    ///
+    ///`detail` has `datasetLabel`, `datasetIndex`, `index`, `raw` and `url`,
+    ///calling `preventDefault()` on the event keeps the page from following the link.
+    pub fn with_event(mut self, name: &str) -> Self {
+        self.event = Some(name.to_string());
+        self
+    }
+
+    /// JS statements resolving `url` for the element `e`, along with `dataset` and `raw`
+    fn resolve_js(&self) -> String {
+        let mut js = "const dataset = chart.data.datasets[e.datasetIndex]; \
+            const raw = dataset.data[e.index]; \
+            let url = (raw && raw.url) || null;".to_string();
+        if !self.category_urls.is_empty() {
+            //index values are always under x, see Dataset parsing, only own keys match so `constructor` or `__proto__` do not
+            js.push_str(&format!(
+                " if (!url && raw && raw.x !== undefined) {{ const urls = {}; \
+                if (Object.prototype.hasOwnProperty.call(urls, raw.x)) {{ url = urls[raw.x] || null; }} }}",
+                js_literal(&self.category_urls)
+            ));
+        }
+        js.push_str(
+            " if (url) { try { const p = new URL(url, window.location.href).protocol; \
+            if (p !== 'http:' && p !== 'https:') { url = null; } } catch (err) { url = null; } }"
+        );
+        js
+    }
+
+    pub(crate) fn on_click_js(&self) -> String {
+        let dispatch = match &self.event {
+            Some(name) => format!(
+                " const ev = new CustomEvent({}, {{ bubbles: true, cancelable: true, detail: {{ \
+                datasetLabel: dataset.label, datasetIndex: e.datasetIndex, index: e.index, raw: raw, url: url }} }}); \
+                if (!chart.canvas.dispatchEvent(ev)) {{ return; }}",
+                js_literal(name)
+            ),
+            None => String::new()
+        };
+        let open = match self.target {
+            LinkTarget::SameTab => "window.location.assign(url);",
+            LinkTarget::NewTab => "window.open(url, '_blank', 'noopener');"
+        };
+        format!(
+            "function(event, elements, chart){{ if (!elements.length) {{ return; }} const e = elements[0]; {}{} if (url) {{ {} }} }}",
+            self.resolve_js(),
+            dispatch,
+            open
+        )
+    }
+
+    /// Pointer cursor over points that link somewhere
+    pub(crate) fn on_hover_js(&self) -> String {
+        format!(
+            "function(event, elements, chart){{ let link = false; if (elements.length) {{ const e = elements[0]; {} link = !!url; }} \
+            chart.canvas.style.cursor = link ? 'pointer' : ''; }}",
+            self.resolve_js()
+        )
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn click_test() {
+        let click = ClickConfig::new()
+            .with_target(LinkTarget::NewTab)
+            .with_category_url("</script>", "/drill/a")
+            .with_event("chart:click");
+        let js = click.on_click_js();
+        assert!(js.contains(r#"const urls = {"<\/script>":"/drill/a"}; if (Object.prototype.hasOwnProperty.call(urls, raw.x)) { url = urls[raw.x] || null; }"#));
+        assert!(js.contains(r#"new CustomEvent("chart:click", "#));
+        assert!(js.ends_with("if (url) { window.open(url, '_blank', 'noopener'); } }"));
+    }
+}
@@ -0,0 +1,94 @@
+use serde::{Serialize, Serializer};
+use serde::ser::Error;
+use crate::{impl_scale_type, ScaleType};
+use crate::serde::{SerializeFormat, WithTypeAndSerializer};
+use crate::time_zone::naive_zone;
+use jiff::{civil, Timestamp, Zoned};
+use jiff::tz::Offset;
+
+impl_scale_type!(Category for civil::Time);
+impl_scale_type!(Time for Timestamp Zoned civil::DateTime civil::Date);
+
+
+
+impl SerializeFormat<Timestamp> for Timestamp{
+    fn serialize<S>(value: &Timestamp, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        //serialize to rfc string
+        value.to_string().serialize(serializer)
+    }
+}
+
+
+impl SerializeFormat<Zoned> for Zoned{
+    fn serialize<S>(value: &Zoned, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        //rfc string with the offset, without the zone name annotation
+        value.timestamp().display_with_offset(value.offset()).to_string().serialize(serializer)
+    }
+}
+
+
+/// Naive date-time is put in the chart's naive time zone, see `ChartConfig::with_naive_time_zone`
+impl SerializeFormat<civil::DateTime> for civil::DateTime{
+    fn serialize<S>(value: &civil::DateTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        let offset = naive_zone().offset_of_local(
+            (value.year() as i32, value.month() as u32, value.day() as u32),
+            (value.hour() as u32, value.minute() as u32, value.second() as u32)
+        );
+        let offset = Offset::from_seconds(offset).map_err(|err| Error::custom(err.to_string()))?;
+        let timestamp = offset.to_timestamp(*value).map_err(|err| Error::custom(err.to_string()))?;
+        timestamp.display_with_offset(offset).to_string().serialize(serializer)
+    }
+}
+
+
+/// Date is the midnight of the day in the chart's naive time zone
+impl SerializeFormat<civil::Date> for civil::Date{
+    fn serialize<S>(value: &civil::Date, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        <civil::DateTime as SerializeFormat<civil::DateTime>>::serialize(&value.to_datetime(civil::Time::midnight()), serializer)
+    }
+}
+
+
+impl SerializeFormat<civil::Time> for civil::Time{
+    fn serialize<S>(value: &civil::Time, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        value.strftime("%H:%M:%S").to_string().serialize(serializer)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use serde_json::to_string;
+    use jiff::civil::date;
+    use crate::serde::{SerializeSettings, SettingsGuard, ValueSerializeWrapper};
+    use crate::time_zone::ChartTimeZone;
+
+    #[test]
+    fn civil_date_time_test() {
+        assert_eq!(to_string(&ValueSerializeWrapper(date(2025, 1, 1).at(10, 0, 0, 0))).unwrap(), r#""2025-01-01T10:00:00+00:00""#);
+        let _guard = SettingsGuard::set(SerializeSettings{ naive_zone: ChartTimeZone::fixed_hours(3), ..SerializeSettings::default() });
+        assert_eq!(to_string(&ValueSerializeWrapper(date(2025, 1, 1))).unwrap(), r#""2025-01-01T00:00:00+03:00""#);
+        assert_eq!(to_string(&ValueSerializeWrapper(date(2025, 1, 1).at(10, 5, 0, 0).time())).unwrap(), r#""10:05:00""#);
+    }
+
+    #[test]
+    fn zoned_test() {
+        let zoned = date(2025, 7, 1).at(10, 0, 0, 0).in_tz("Europe/Berlin").unwrap();
+        assert_eq!(to_string(&ValueSerializeWrapper(zoned)).unwrap(), r#""2025-07-01T10:00:00+02:00""#);
+    }
+}
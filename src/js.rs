@@ -0,0 +1,114 @@
+use std::cell::RefCell;
+use std::fmt::Write;
+use sailfish::RenderError;
+use sailfish::runtime::{Buffer, Render};
+use serde::{Serialize, Serializer};
+use uuid::Uuid;
+
+/// Raw JS collected while a value is serialized with [`to_js`].
+///
+/// serde_json can only produce JSON, so raw code (callbacks, scriptable options) is serialized
+/// as a placeholder string that gets swapped for the code once serialization is done.
+/// Placeholders carry a random nonce, so no user-provided string can be mistaken for one.
+struct RawJsContext {
+    nonce: String,
+    chunks: Vec<String>
+}
+
+thread_local! {
+    static RAW_JS: RefCell<Option<RawJsContext>> = const { RefCell::new(None) };
+}
+
+/// Serializes `code` as raw JS when rendered through [`to_js`], as a plain string otherwise
+pub(crate) fn serialize_raw<S>(code: &str, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer
+{
+    let placeholder = RAW_JS.with(|ctx| {
+        ctx.borrow_mut().as_mut().map(|ctx| {
+            ctx.chunks.push(code.to_string());
+            format!("__js_{}_{}__", ctx.nonce, ctx.chunks.len() - 1)
+        })
+    });
+    match placeholder {
+        Some(p) => serializer.serialize_str(&p),
+        None => serializer.serialize_str(code)
+    }
+}
+
+/// Encodes a Rust value as a JS literal, safe to embed into generated code
+pub(crate) fn js_literal<T: Serialize + ?Sized>(value: &T) -> String {
+    try_js_literal(value).expect("plain values always serialize")
+}
+
+/// [`js_literal`] for values that may fail to serialize, like maps with non-string keys
+pub(crate) fn try_js_literal<T: Serialize + ?Sized>(value: &T) -> Result<String, serde_json::Error> {
+    Ok(serde_json::to_string(value)?.replace("</", "<\\/"))
+}
+
+/// Serializes value to a JS object literal, with raw JS where the value asked for it
+pub(crate) fn to_js<T: Serialize + ?Sized>(value: &T) -> Result<String, serde_json::Error> {
+    let nonce = Uuid::new_v4().simple().to_string();
+    let previous = RAW_JS.with(|ctx| ctx.replace(Some(RawJsContext { nonce: nonce.clone(), chunks: vec![] })));
+    let json = serde_json::to_string(value);
+    let ctx = RAW_JS.with(|ctx| ctx.replace(previous)).expect("raw js context is set above");
+    //JSON only has "</" inside of strings, escape it so a value can't close the script tag
+    let mut json = json?.replace("</", "<\\/");
+    for (i, chunk) in ctx.chunks.iter().enumerate() {
+        json = json.replacen(&format!("\"__js_{}_{}__\"", nonce, i), chunk, 1);
+    }
+    Ok(json)
+}
+
+/// Renders the wrapped value in a template with [`to_js`]
+pub(crate) struct Js<'a, T: Serialize + ?Sized>(pub &'a T);
+
+impl<T: Serialize + ?Sized> Render for Js<'_, T> {
+    fn render(&self, b: &mut Buffer) -> Result<(), RenderError> {
+        let js = to_js(self.0).map_err(|e| RenderError::Msg(e.to_string()))?;
+        b.write_str(&js)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{JsFunction, JsFunctionError};
+
+    struct Raw(&'static str);
+
+    impl Serialize for Raw {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize_raw(self.0, serializer)
+        }
+    }
+
+    #[test]
+    fn raw_js_test() {
+        let value = vec![(Raw("function(v){ return v; }"), "__js_not_a_placeholder__</script>")];
+        assert_eq!(to_js(&value).unwrap(), r#"[[function(v){ return v; },"__js_not_a_placeholder__<\/script>"]]"#);
+        //outside of to_js raw code is a plain string
+        assert_eq!(serde_json::to_string(&Raw("a")).unwrap(), r#""a""#);
+    }
+
+    #[test]
+    fn js_function_test() {
+        let unit = "</script><script>alert('kWh')".to_string();
+        let expr = JsFunction::new(&["value"]).unwrap()
+            .bind("unit", &unit).unwrap()
+            .bind("limit", &[1.5, 2.0]).unwrap()
+            .body("return value + ' ' + unit;");
+        assert_eq!(
+            expr.as_str(),
+            r#"function(value){ const unit = "<\/script><script>alert('kWh')"; const limit = [1.5,2.0]; return value + ' ' + unit; }"#
+        );
+        assert!(matches!(JsFunction::new(&["value", "value"]), Err(JsFunctionError::DuplicateName("value"))));
+        assert!(matches!(JsFunction::new(&["a-b"]), Err(JsFunctionError::InvalidName("a-b"))));
+        let f = || JsFunction::new(&["value"]).unwrap();
+        assert!(matches!(f().bind("class", &1), Err(JsFunctionError::ReservedName("class"))));
+        assert!(matches!(f().bind("value", &1), Err(JsFunctionError::DuplicateName("value"))));
+        assert!(matches!(f().bind("a", &1).unwrap().bind("a", &2), Err(JsFunctionError::DuplicateName("a"))));
+        assert!(matches!(f().bind("m", &std::collections::HashMap::from([((1, 2), 3)])), Err(JsFunctionError::Value(_))));
+    }
+}
@@ -2,8 +2,18 @@ mod options;
 
 pub mod render;
 pub mod common;
+pub mod format;
+pub mod time_zone;
+pub mod palette;
+pub mod annotation;
+pub mod zoom;
+pub mod datalabels;
+pub mod plugin;
+pub mod tooltip;
+pub mod interaction;
 
 pub use options::*;
+pub use data::PointMeta;
 
 #[cfg(feature = "time_axis")]
 pub mod time_axis;
@@ -11,8 +21,15 @@ pub mod time_axis;
 
 #[cfg(feature = "chrono_axis")]
 pub mod chrono_axis;
+
+#[cfg(feature = "jiff_axis")]
+pub mod jiff_axis;
+
+#[cfg(feature = "decimal")]
+pub mod decimal;
 mod serde;
 mod data;
+mod js;
 
 #[cfg(test)]
 mod tests {
@@ -29,4 +46,137 @@ mod tests {
         assert_eq!(deserialized, color);
         println!("Deserialized: {:?}", deserialized);
     }
+
+    #[test]
+    fn color_test(){
+        use common::Color;
+        for css in ["rgba(255, 0, 0, 0.5)", "#336699", "#33669980", "hsl(210, 50%, 40%)", "hsla(210, 50%, 40%, 0.3)", "steelblue"] {
+            let color: Color = serde_json::from_str(&format!("\"{}\"", css)).unwrap();
+            assert_eq!(serde_json::to_string(&color).unwrap(), format!("\"{}\"", css));
+        }
+        assert_eq!(Color::hex("#fff").unwrap(), Color::Hex(255, 255, 255, 255));
+        assert!("rgb(300, 0, 0)".parse::<Color>().is_err());
+        assert_eq!("rgb(100%, 0%, 50%)".parse::<Color>().unwrap(), Color::Rgba(255, 0, 128, 1.0));
+        assert_eq!("rgba(255, 0, 0, 50%)".parse::<Color>().unwrap(), Color::Rgba(255, 0, 0, 0.5));
+        assert_eq!("hsla(210, 50%, 40%, 30%)".parse::<Color>().unwrap(), Color::Hsla(210.0, 50.0, 40.0, 0.3));
+        for invalid in ["rgba(0, 0, 0, 7)", "rgba(0, 0, 0, NaN)", "rgb(120%, 0%, 0%)", "hsl(400, 50%, 40%)", "hsl(210, 150%, 40%)", "hsla(210, 50%, 40%, 2)"] {
+            assert!(invalid.parse::<Color>().is_err(), "{}", invalid);
+        }
+        assert_eq!(Color::named("SteelBlue").unwrap().to_rgba(), (70, 130, 180, 1.0));
+        assert_eq!(Color::Hsl(0.0, 100.0, 50.0).to_rgba(), (255, 0, 0, 1.0));
+        assert_eq!(Color::rgb(255, 0, 0).darken(0.25).to_rgba(), (128, 0, 0, 1.0));
+        assert_eq!(Color::rgb(0, 0, 0).mix(&Color::named("white").unwrap(), 0.5).with_alpha(0.2).to_string(), "rgba(128, 128, 128, 0.2)");
+    }
+
+    #[test]
+    fn horizontal_bar_test(){
+        use sailfish::TemplateSimple;
+        let html = ChartConfig::<&str, f64>::default()
+            .with_index_axis(AxisName::Y)
+            .add_series(ChartType::Bar, "set".to_string(), [("First", 1.0), ("Second", 2.0)])
+            .set_x_axis(ScaleConfig::new_category(false, vec!["First", "Second"]))
+            .build(common::Size::pixels(600), common::Size::pixels(400))
+            .render_once()
+            .unwrap();
+        assert!(html.contains(r#"indexAxis: "y""#));
+        assert!(html.contains(r#""data":[{"x":"First","y":1.0},{"x":"Second","y":2.0}],"parsing":{"xAxisKey":"y","yAxisKey":"x"}"#));
+        //category scale goes to the vertical axis, value scale to the horizontal one
+        assert!(html.contains(r#"scales: {"x":{"type":"linear","reverse":false},"y":{"type":"category","labels":["First","Second"],"reverse":false}}"#));
+    }
+
+    #[test]
+    fn dataset_index_axis_test(){
+        use sailfish::TemplateSimple;
+        //a horizontal dataset in a vertical chart and the other way around, each read by its own index axis
+        let html = ChartConfig::<&str, f64>::default()
+            .add_series_direct(Dataset::new(ChartType::Bar, "h".to_string(), [("A", 1.0)]).with_index_axis(AxisName::Y))
+            .add_series(ChartType::Bar, "v".to_string(), [("A", 2.0)])
+            .build(common::Size::pixels(600), common::Size::pixels(400))
+            .render_once()
+            .unwrap();
+        assert!(html.contains(r#""label":"h","data":[{"x":"A","y":1.0}],"indexAxis":"y","parsing":{"xAxisKey":"y","yAxisKey":"x"}"#));
+        assert!(html.contains(r#""label":"v","data":[{"x":"A","y":2.0}]}"#));
+        let html = ChartConfig::<&str, f64>::default()
+            .with_index_axis(AxisName::Y)
+            .add_series_direct(Dataset::new(ChartType::Bar, "v".to_string(), [("A", 1.0)]).with_index_axis(AxisName::X))
+            .build(common::Size::pixels(600), common::Size::pixels(400))
+            .render_once()
+            .unwrap();
+        assert!(html.contains(r#""data":[{"x":"A","y":1.0}],"indexAxis":"x"}"#));
+    }
+
+    #[test]
+    fn zero_line_test(){
+        let grid = GridConfig::default()
+            .with_color(common::Rgb(200, 200, 200))
+            .with_line_width(PerTick::ZeroLine{ zero: 3.0, other: 1.0 });
+        assert_eq!(
+            js::to_js(&grid).unwrap(),
+            r#"{"color":"rgb(200, 200, 200)","lineWidth":function(context){ return context.tick && context.tick.value === 0 ? 3.0 : 1.0; }}"#
+        );
+    }
+
+    #[test]
+    fn scale_range_test(){
+        let scale = ScaleConfig::<f64>::default()
+            .with_begin_at_zero(true)
+            .with_suggested_min(-5.0)
+            .with_suggested_max(50.0)
+            .with_grace(Grace::Percent(common::Percent(10.0)))
+            .with_bounds(Bounds::Ticks)
+            .with_offset(false)
+            .with_weight(2);
+        assert_eq!(
+            js::to_js(&scale).unwrap(),
+            r#"{"reverse":false,"suggestedMax":50.0,"suggestedMin":-5.0,"beginAtZero":true,"grace":"10%","bounds":"ticks","offset":false,"weight":2}"#
+        );
+        let scale = ScaleConfig::<f64>::default().with_grace(Grace::Absolute(2.5));
+        assert_eq!(js::to_js(&scale).unwrap(), r#"{"reverse":false,"grace":2.5}"#);
+    }
+
+    #[test]
+    fn date_adapter_test(){
+        use std::time::SystemTime;
+        let chart = ChartConfig::<SystemTime, f64>::default()
+            .build(common::Size::pixels(600), common::Size::pixels(400));
+        assert_eq!(chart.dependencies().scripts(), [render::DATE_ADAPTER_SCRIPT.into()]);
+        let chart = ChartConfig::<f64, f64>::default()
+            .build(common::Size::pixels(600), common::Size::pixels(400));
+        assert!(chart.dependencies().is_empty());
+
+        //a chart rendered on its own loads the adapter itself, a page loads it once for all of its charts
+        use sailfish::TemplateSimple;
+        let time_chart = || ChartConfig::<SystemTime, f64>::default()
+            .add_series(ChartType::Line, "set".to_string(), [(SystemTime::UNIX_EPOCH, 1.0)])
+            .build(common::Size::pixels(600), common::Size::pixels(400));
+        let tag = format!("<script src=\"{}\"></script>", render::DATE_ADAPTER_SCRIPT);
+        assert_eq!(time_chart().render_once().unwrap().matches(&tag).count(), 1);
+        assert!(!time_chart().without_dependencies().render_once().unwrap().contains(&tag));
+        let page = render::OnePage::new("page", "")
+            .add_chart(time_chart()).unwrap()
+            .add_chart(time_chart()).unwrap()
+            .render_once().unwrap();
+        assert_eq!(page.matches(&tag).count(), 1);
+    }
+
+    #[test]
+    fn instant_render_test(){
+        use std::time::{Duration, Instant};
+        use sailfish::TemplateSimple;
+        let start = Instant::now();
+        let data = [(start, 1.0), (start + Duration::from_secs(60), 2.0)];
+        let render = || {
+            let html = ChartConfig::<Duration, f64>::default()
+                .add_instant_series(ChartType::Line, "set".to_string(), None, data)
+                .build(common::Size::pixels(600), common::Size::pixels(400))
+                .render_once()
+                .unwrap();
+            let data = &html[html.find("data: ").unwrap()..];
+            data[..data.find('\n').unwrap()].to_string()
+        };
+        let first = render();
+        assert_eq!(first, r#"data: {"datasets":[{"type":"line","label":"set","data":[{"x":0.0,"y":1.0},{"x":60000.0,"y":2.0}]}]},"#);
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(render(), first);
+    }
 }
@@ -1,23 +1,42 @@
+use std::borrow::Cow;
 use std::cmp::PartialEq;
-use crate::common::{Padding, Rgb, Size};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use crate::common::{Color, Font, Padding, Paint, Percent, Rgb, Size};
+use crate::palette::{AutoColors, ColorLegend, ColorScale};
+use crate::annotation::{AnnotationPlugin, Annotations};
+use crate::zoom::{ZoomConfig, ZoomPlugin};
+use crate::datalabels::{DataLabels, DataLabelsPlugin, LabelDisplay};
+use crate::tooltip::{ExternalTooltip, TooltipLayout};
+use crate::interaction::ClickConfig;
+use crate::plugin::{AttachedPlugin, ChartPlugin, Dependencies};
+use crate::format::NumberFormat;
+use crate::js::{js_literal, serialize_raw, to_js, try_js_literal};
+use crate::time_zone::{luxon_format, ChartTimeZone};
+#[cfg(feature = "decimal")]
+use crate::decimal::DecimalRounding;
 use crate::render::Chart;
 use ndarray::{Array1, Array2};
 use ndarray_linalg::error::LinalgError;
 use ndarray_linalg::LeastSquaresSvd;
-use ndarray_linalg::{Lapack, Scalar};
-use serde::Serialize;
+use serde::{Serialize, Serializer};
+use serde::ser::SerializeMap;
 use serde::Deserialize;
 use uuid::Uuid;
 use crate::data::ChartData;
-use crate::serde::{ValueSerializeWrapper, WithTypeAndSerializer};
+use crate::serde::{settings, SerializeSettings, ValueSerializeWrapper, WithTypeAndSerializer};
 
-const DISPLAY_FN: &'static str = "
+const DISPLAY_FN: &str = "
                         function(context){
                             context = context[0];
                             let ttp = context.raw.tooltip || '';
                             if(ttp) return ttp;
                         }";
 
+/// Chart with `X` values on the index axis and `Y` values on the value axis.
+///
+/// A category `Y` scale is no longer reversed by default, charts with categories on the vertical axis
+/// should use e.g. `ChartConfig<&str, f64>` with `with_index_axis(AxisName::Y)` and (category, value) data.
 #[derive(Debug, Clone)]
 pub struct ChartConfig<X:WithTypeAndSerializer+Serialize,Y:WithTypeAndSerializer+Serialize>
 {
@@ -36,30 +55,68 @@ where X:WithTypeAndSerializer+Serialize, Y:WithTypeAndSerializer+Serialize{
     }
 
     pub fn set_x_axis(mut self, conf: ScaleConfig<X>) -> Self {
-        let mut scales = self.options.scales;
-        if scales.is_none() {
-            scales = Some(ScalingConfig{
-                x: Some(conf),
-                y: None
-            });
-        }else {
-            scales.as_mut().unwrap().x = Some(conf);
-        }
-        self.options.scales = scales;
+        let scales = self.options.scales.get_or_insert_with(|| ScalingConfig{
+            x: None,
+            y: None
+        });
+        scales.x = Some(conf);
+        self.options.apply_time_zone();
         self
     }
 
     pub fn set_y_axis(mut self, conf: ScaleConfig<Y>) -> Self {
-        let mut scales = self.options.scales;
-        if scales.is_none() {
-            scales = Some(ScalingConfig{
-                x: None,
-                y: Some(conf)
-            });
-        }else {
-            scales.as_mut().unwrap().y = Some(conf);
-        }
-        self.options.scales = scales;
+        let scales = self.options.scales.get_or_insert_with(|| ScalingConfig{
+            x: None,
+            y: None
+        });
+        scales.y = Some(conf);
+        self.options.apply_time_zone();
+        self
+    }
+
+    /// Time zone ticks and tooltips of time scales are displayed in, browser local time if not set.
+    ///
+    /// Time scales are switched to the luxon date adapter, their date formats are translated to its syntax.
+    pub fn with_time_zone<Z: Into<ChartTimeZone>>(mut self, zone: Z) -> Self {
+        self.options.time_zone = Some(zone.into());
+        self.options.apply_time_zone();
+        self
+    }
+
+    /// Time zone naive date-times (without an offset) are assumed to be in.
+    ///
+    /// When not set, the display time zone is used, and if that is not set either, UTC:
+    /// naive values are never read in the browser's local time.
+    pub fn with_naive_time_zone<Z: Into<ChartTimeZone>>(mut self, zone: Z) -> Self {
+        self.options.naive_time_zone = Some(zone.into());
+        self
+    }
+
+    /// What to do with NaN and infinite floats, they are sent as gaps (`null`) by default
+    pub fn with_non_finite_policy(mut self, policy: NonFinitePolicy) -> Self {
+        self.options.non_finite = policy;
+        self
+    }
+
+    /// Rounding applied to decimal values when they are serialized, full precision if not set
+    #[cfg(feature = "decimal")]
+    pub fn with_decimal_rounding(mut self, rounding: DecimalRounding) -> Self {
+        self.options.decimal_rounding = Some(rounding);
+        self
+    }
+
+    /// Sets the index axis of the chart, `AxisName::Y` gives horizontal bars.
+    ///
+    /// Data is always passed as (index, value), so `X` stays the index (category) axis
+    /// and `Y` the value axis. When the index axis is `Y` the crate maps the `X` scale
+    /// to the vertical axis and the `Y` scale to the horizontal one by itself,
+    /// there is no need to reverse the category labels.
+    ///
+    /// Charts that put categories on the vertical axis through a `Y` category type, like `ChartConfig<f64, &str>`
+    /// with (value, category) data, used to get a reversed scale and now render the categories bottom up,
+    /// they should switch to `ChartConfig<&str, f64>` with `AxisName::Y` and (category, value) data.
+    pub fn with_index_axis(mut self, axis: AxisName) -> Self {
+        self.options.index_axis = Some(axis);
         self
     }
 
@@ -80,22 +137,12 @@ where X:WithTypeAndSerializer+Serialize, Y:WithTypeAndSerializer+Serialize{
     }
 
     pub fn add_series_with_config<T: Into<ChartData<X,Y>>>(mut self, r#type: ChartType, title:String, config:ElementsConfig, data: T)->Self{
-        self.data.datasets.push(Dataset {
-            r#type,
-            label: title,
-            data: data.into(),
-            elements: Some(config)
-        });
+        self.data.datasets.push(Dataset::new(r#type, title, data).with_elements(config));
         self
     }
 
     pub fn add_series<T: Into<ChartData<X,Y>>>(mut self, r#type: ChartType, title:String, data: T)->Self{
-        self.data.datasets.push(Dataset {
-            r#type,
-            label: title,
-            data: data.into(),
-            elements: None
-        });
+        self.data.datasets.push(Dataset::new(r#type, title, data));
         self
     }
 
@@ -124,91 +171,302 @@ where X:WithTypeAndSerializer+Serialize, Y:WithTypeAndSerializer+Serialize{
         self.options.plugins.subtitle = Some(subtitle);
         self
     }
+
+    /// Lines, boxes and labels over the chart area, through chartjs-plugin-annotation
+    pub fn with_annotations(mut self, annotations: Annotations<X,Y>) -> Self{
+        self.options.annotation = Some(annotations);
+        self
+    }
+
+    /// Zoom and pan, through chartjs-plugin-zoom
+    pub fn with_zoom(mut self, zoom: ZoomConfig<X,Y>) -> Self{
+        self.options.zoom = Some(zoom);
+        self
+    }
+
+    /// Value labels on every dataset, through chartjs-plugin-datalabels. Datasets can override them.
+    pub fn with_data_labels(mut self, labels: DataLabels) -> Self{
+        self.options.plugins.datalabels = Some(labels);
+        self
+    }
+
+    /// The datalabels plugin is on for the chart, labels are set on the chart or on one of the datasets
+    pub(crate) fn uses_data_labels(&self) -> bool {
+        self.options.plugins.datalabels.is_some() || self.data.datasets.iter().any(|d| d.datalabels.is_some())
+    }
+
+    /// Plugin with its default options, its scripts are loaded along with the chart
+    pub fn with_plugin<P: ChartPlugin + ?Sized>(mut self, plugin: &P) -> Self {
+        self.options.plugins.custom.push(AttachedPlugin::new(plugin));
+        self
+    }
+
+    /// Plugin with options, serialized under the plugin's options key
+    pub fn with_plugin_options<P, O>(mut self, plugin: &P, options: &O) -> Result<Self, serde_json::Error>
+    where P: ChartPlugin + ?Sized, O: Serialize + ?Sized
+    {
+        self.options.plugins.custom.push(AttachedPlugin::new(plugin).with_options(options)?);
+        Ok(self)
+    }
+
+    /// Plugins enabled through the chart's `plugins` array rather than registered globally
+    pub(crate) fn local_plugins(&self) -> Vec<Cow<'static, str>> {
+        let mut local = vec![];
+        if self.uses_data_labels() {
+            local.extend(DataLabelsPlugin.local_instance());
+        }
+        local.extend(self.options.plugins.custom.iter().filter_map(|p| p.local_instance.clone()));
+        local
+    }
+
+    /// Plugin scripts and registrations the chart needs, see `Chart::dependencies`
+    pub(crate) fn plugin_dependencies(&self, dependencies: &mut Dependencies) {
+        if self.options.annotation.is_some() {
+            dependencies.add_plugin(&AnnotationPlugin);
+        }
+        if self.uses_data_labels() {
+            dependencies.add_plugin(&DataLabelsPlugin);
+        }
+        if self.options.zoom.is_some() {
+            dependencies.add_plugin(&ZoomPlugin);
+        }
+        for plugin in &self.options.plugins.custom {
+            dependencies.add_attached(plugin);
+        }
+    }
     
-    pub fn build(self, width: Size, height: Size) -> Chart<X,Y>{
+    /// Color bar under the chart, for datasets colored with `Dataset::with_value_colors`
+    pub fn with_color_legend(mut self, legend: ColorLegend) -> Self {
+        self.options.color_legend = Some(legend);
+        self
+    }
+
+    /// Links and events on clicked data points
+    pub fn with_click(mut self, click: ClickConfig) -> Self {
+        self.options.on_click = Some(click);
+        self
+    }
+
+    /// Datasets without their own border or background color get one from the palette
+    pub fn with_auto_colors(mut self, colors: AutoColors) -> Self {
+        self.options.auto_colors = Some(colors);
+        self
+    }
+
+    pub fn build(mut self, width: Size, height: Size) -> Chart<X,Y>{
+        for dataset in self.data.datasets.iter_mut() {
+            dataset.parsing = Parsing::for_index_axis(dataset.index_axis.or(self.options.index_axis));
+        }
+        for dataset in self.data.datasets.iter_mut() {
+            dataset.apply_value_colors(self.options.non_finite);
+        }
+        //the plugin is enabled for the whole chart, datasets without labels of their own are left bare
+        if self.options.plugins.datalabels.is_none() && self.uses_data_labels() {
+            self.options.plugins.datalabels = Some(DataLabels::default().with_display(LabelDisplay::Never));
+            for labels in self.data.datasets.iter_mut().filter_map(|d| d.datalabels.as_mut()) {
+                labels.shown_by_default();
+            }
+        }
+        if let Some(tooltip) = self.options.plugins.tooltip.as_mut() {
+            tooltip.map_deprecated_mode();
+        }
+        if let Some(colors) = &self.options.auto_colors {
+            let labels: Vec<&str> = self.data.datasets.iter().map(|d| d.label.as_str()).collect();
+            let picked: Vec<Rgb> = colors.colors_for(&labels).into_iter().cloned().collect();
+            for (dataset, color) in self.data.datasets.iter_mut().zip(picked) {
+                dataset.apply_auto_color(color, colors.background_alpha());
+            }
+        }
         Chart::new(Uuid::new_v4().to_string(), width, height, self)
     }
 }
 
 
-impl<X> ChartConfig<X, X> where  X:WithTypeAndSerializer + Scalar + Lapack + Clone + Into<f64> {
-    
-    pub fn add_linear_regression_series<T: Into<ChartData<X,X>>>(self, title: &str, data: T) -> Result<Self, LinalgError> {
+impl<Y> ChartConfig<Duration, Y> where Y:WithTypeAndSerializer+Serialize {
+
+    /// Adds a series of instants, plotted as time passed since `reference`.
+    ///
+    /// Without a reference the earliest instant of the series is used,
+    /// pass the same reference to all series to line them up. Instants before the reference are put at 0.
+    pub fn add_instant_series<T: IntoIterator<Item=(Instant, Y)>>(self, r#type: ChartType, title: String, reference: Option<Instant>, data: T) -> Self {
+        let data: Vec<(Instant, Y)> = data.into_iter().collect();
+        let reference = reference.or_else(|| data.iter().map(|(i, _)| *i).min());
+        let relative: Vec<(Duration, Y)> = data.into_iter()
+            .map(|(i, y)| (reference.map_or(Duration::ZERO, |r| i.saturating_duration_since(r)), y))
+            .collect();
+        self.add_series(r#type, title, relative)
+    }
+}
+
+
+/// Explicit conversion to and from f64, regression and statistics helpers compute in f64
+pub trait AsF64 {
+    fn as_f64(&self) -> f64;
+
+    ///`None` when the type can't represent the value, like NaN or an out of range value for decimals
+    fn from_f64(value: f64) -> Option<Self> where Self: Sized;
+}
+
+impl AsF64 for f64 {
+    fn as_f64(&self) -> f64 {
+        *self
+    }
+
+    fn from_f64(value: f64) -> Option<Self> {
+        Some(value)
+    }
+}
+
+impl AsF64 for f32 {
+    fn as_f64(&self) -> f64 {
+        *self as f64
+    }
+
+    ///`None` for finite values beyond the f32 range, NaN and infinities carry over
+    fn from_f64(value: f64) -> Option<Self> {
+        let cast = value as f32;
+        (cast.is_finite() || !value.is_finite()).then_some(cast)
+    }
+}
+
+/// Regression series that could not be computed
+#[derive(Debug)]
+pub enum RegressionError {
+    Linalg(LinalgError),
+    ///value that is not finite in f64, or that the data type can't represent, with `NonFinitePolicy::Error`
+    NonFinite(f64)
+}
+
+impl From<LinalgError> for RegressionError {
+    fn from(value: LinalgError) -> Self {
+        RegressionError::Linalg(value)
+    }
+}
+
+impl std::fmt::Display for RegressionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegressionError::Linalg(err) => write!(f, "regression failed: {}", err),
+            RegressionError::NonFinite(value) => write!(f, "non-finite value {} in regression", value)
+        }
+    }
+}
+
+impl std::error::Error for RegressionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RegressionError::Linalg(err) => Some(err),
+            RegressionError::NonFinite(_) => None
+        }
+    }
+}
+
+
+impl<X> ChartConfig<X, X> where  X:WithTypeAndSerializer + Serialize + AsF64 + Clone {
+
+    /// Adds the points as a scatter series and their least squares line as a line series.
+    ///
+    /// Points that are not finite in f64 are left out of the fit, and line points the data type can't represent
+    /// are left out of the line, unless the chart's `NonFinitePolicy` (set before this call) is `Error`.
+    pub fn add_linear_regression_series<T: Into<ChartData<X,X>>>(self, title: &str, data: T) -> Result<Self, RegressionError> {
         let data:Vec<(X,X)>  = data.into().into();
-        let n = data.len();
-        let config_with_scatter = self.add_series(ChartType::Scatter, title.to_string(), data.clone());
-        
+        let strict = self.options.non_finite == NonFinitePolicy::Error;
+        let fit = regression_input(&data, strict)?;
+        let n = fit.len();
+
         // Allocate design matrix with shape (n, 2)
-        let mut x_matrix = Array2::<X>::zeros((n, 2));
-        let mut y_array = Array1::<X>::zeros(n);
+        let mut x_matrix = Array2::<f64>::zeros((n, 2));
+        let mut y_array = Array1::<f64>::zeros(n);
 
-        for (i, (x, y)) in data.into_iter().enumerate() {
-            x_matrix[[i, 0]] = X::one(); // intercept term
-            x_matrix[[i, 1]] = x;
-            y_array[i] = y;
+        for (i, (_, x, y)) in fit.iter().enumerate() {
+            x_matrix[[i, 0]] = 1.0; // intercept term
+            x_matrix[[i, 1]] = *x;
+            y_array[i] = *y;
         }
         
         let beta = x_matrix.least_squares(&y_array)?.solution;
 
         let y_pred = x_matrix.dot(&beta);
-        let r2 = Self::r_squared(&y_array, &y_pred);
-        let mut reg_data = Vec::<(X,X)>::with_capacity(n);
-        
-        y_pred.into_iter().enumerate().for_each(|(i, x)| {
-            reg_data.push((x_matrix[[i,1]],x));
-        });
-        
+        let r2 = r_squared(&y_array, &y_pred);
+        let reg_data = regression_line(&fit, y_pred.iter().copied(), strict)?;
+
+        let config_with_scatter = self.add_series(ChartType::Scatter, title.to_string(), data);
         let config_with_both_charts = 
             config_with_scatter.add_series(ChartType::Line, format!("{} regression(R^2 = {:.4})", title, r2), reg_data);
         
         Ok(config_with_both_charts)
     }
+}
+
+/// Points to fit with their f64 values, non-finite ones are left out unless `strict`
+pub(crate) fn regression_input<X: AsF64>(data: &[(X, X)], strict: bool) -> Result<Vec<(&X, f64, f64)>, RegressionError> {
+    let mut fit = Vec::with_capacity(data.len());
+    for (x, y) in data {
+        let (x_f64, y_f64) = (x.as_f64(), y.as_f64());
+        match (x_f64.is_finite(), y_f64.is_finite()) {
+            (true, true) => fit.push((x, x_f64, y_f64)),
+            (false, _) if strict => return Err(RegressionError::NonFinite(x_f64)),
+            (_, false) if strict => return Err(RegressionError::NonFinite(y_f64)),
+            _ => {}
+        }
+    }
+    Ok(fit)
+}
 
+/// Fitted values back in the data type, values it can't represent are left out unless `strict`
+pub(crate) fn regression_line<X: AsF64 + Clone, P: IntoIterator<Item=f64>>(fit: &[(&X, f64, f64)], predicted: P, strict: bool) -> Result<Vec<(X, X)>, RegressionError> {
+    let mut line = Vec::with_capacity(fit.len());
+    for ((x, _, _), y) in fit.iter().zip(predicted) {
+        match X::from_f64(y) {
+            Some(value) => line.push(((*x).clone(), value)),
+            None if strict => return Err(RegressionError::NonFinite(y)),
+            None => {}
+        }
+    }
+    Ok(line)
+}
 
-    fn r_squared(y_true: &Array1<X>, y_pred: &Array1<X>) -> f64
-    {
-        let n = y_true.len();
-        if n == 0 {
-            return 0.0; // or maybe panic/error, depending on your use case
-        }
-        let n_f = n as f64;
-
-        // Calculate mean once
-        let y_mean = y_true.iter().map(|&v| v.into()).sum::<f64>() / n_f;
-
-        // Sum of squared residuals (errors)
-        let ss_res = y_true
-            .iter()
-            .zip(y_pred.iter())
-            .map(|(&y, &y_hat)| {
-                let y_f = y.into();
-                let y_hat_f = y_hat.into();
-                let diff = y_f - y_hat_f;
-                diff * diff
-            })
-            .sum::<f64>();
-
-        // Total sum of squares
-        let ss_tot = y_true
-            .iter()
-            .map(|&y| {
-                let y_f = y.into();
-                let diff = y_f - y_mean;
-                diff * diff
-            })
-            .sum::<f64>();
-        
-        if ss_tot == 0.0 {
-            // All y_true are constant
-            return if ss_res == 0.0 {
-                1.0  // Perfect fit
-            } else {
-                0.0  // Model fails to match - treat as no explanatory power
-            }
+
+fn r_squared(y_true: &Array1<f64>, y_pred: &Array1<f64>) -> f64
+{
+    let n = y_true.len();
+    if n == 0 {
+        return 0.0; // or maybe panic/error, depending on your use case
+    }
+    let n_f = n as f64;
+
+    // Calculate mean once
+    let y_mean = y_true.sum() / n_f;
+
+    // Sum of squared residuals (errors)
+    let ss_res = y_true
+        .iter()
+        .zip(y_pred.iter())
+        .map(|(&y, &y_hat)| {
+            let diff = y - y_hat;
+            diff * diff
+        })
+        .sum::<f64>();
+
+    // Total sum of squares
+    let ss_tot = y_true
+        .iter()
+        .map(|&y| {
+            let diff = y - y_mean;
+            diff * diff
+        })
+        .sum::<f64>();
+    
+    if ss_tot == 0.0 {
+        // All y_true are constant
+        return if ss_res == 0.0 {
+            1.0  // Perfect fit
+        } else {
+            0.0  // Model fails to match - treat as no explanatory power
         }
-        
-        1.0 - ss_res / ss_tot
     }
+    
+    1.0 - ss_res / ss_tot
 }
 
 
@@ -228,22 +486,61 @@ impl<X: WithTypeAndSerializer+Serialize, Y: WithTypeAndSerializer+Serialize> Def
                 scales: Some(ScalingConfig{
                     x:Some(ScaleConfig{
                         r#type: Some(X::scale_type()),
+                        ticks: X::tick_format().map(|f| TickConfig::default().with_format(f)),
                         ..ScaleConfig::default()
                     }),
                     y:Some(ScaleConfig{
                         r#type: Some(Y::scale_type()),
-                        reverse: Y::scale_type() == ScaleType::Category,
+                        ticks: Y::tick_format().map(|f| TickConfig::default().with_format(f)),
                         ..ScaleConfig::default()
                     }),
                 }),
+                index_axis: None,
+                time_zone: None,
+                naive_time_zone: None,
+                #[cfg(feature = "decimal")]
+                decimal_rounding: None,
+                non_finite: NonFinitePolicy::default(),
+                auto_colors: None,
+                color_legend: None,
                 aspect_ratio: None,
                 elements: None,
+                on_click: None,
                 plugins: Plugins::default(),
+                annotation: None,
+                zoom: None,
             },
         }
     }
 }
 
+/// Number of values given to `Dataset::with_value_colors` differs from the number of points
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueColorsError {
+    pub points: usize,
+    pub values: usize
+}
+
+impl std::fmt::Display for ValueColorsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} color values for {} points", self.values, self.points)
+    }
+}
+
+impl std::error::Error for ValueColorsError {}
+
+/// What happens to NaN and infinite float values, JSON has no way to express them
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum NonFinitePolicy{
+    ///sent as `null`, a gap in the line
+    #[default]
+    Null,
+    ///points with a non-finite value are left out
+    Skip,
+    ///rendering fails
+    Error
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum ChartType {
@@ -273,108 +570,649 @@ pub struct Dataset<X:WithTypeAndSerializer,Y:WithTypeAndSerializer>{
 
     data: ChartData<X,Y>,
 
+    ///overrides the chart-level index axis for this dataset only
     #[serde(skip_serializing_if = "Option::is_none")]
-    elements: Option<ElementsConfig>
-}
+    index_axis: Option<AxisName>,
 
-#[derive(Serialize, Debug, Clone,Default)]
-#[serde(rename_all = "camelCase")]
-pub struct ElementsConfig{
+    ///from the index axis of the dataset, or of the chart, set by `ChartConfig::build`
     #[serde(skip_serializing_if = "Option::is_none")]
-    line:  Option<LineConfig>,
+    parsing: Option<Parsing>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
-    point: Option<PointConfig>
+    span_gaps: Option<SpanGaps>,
+
+    #[serde(flatten)]
+    style: DatasetStyle,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    datalabels: Option<DataLabels>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    elements: Option<ElementsConfig>,
+
+    ///one color per point, turned into the point colors by `ChartConfig::build`
+    #[serde(skip)]
+    value_colors: Option<Vec<Color>>
 }
 
-impl ElementsConfig {
+impl<X,Y> Dataset<X,Y> where X:WithTypeAndSerializer, Y:WithTypeAndSerializer{
 
-    pub fn with_line_config(mut self, conf: LineConfig) -> Self{
-        self.line = Some(conf);
+    pub fn new<T: Into<ChartData<X,Y>>>(r#type: ChartType, label: String, data: T) -> Self {
+        Self {
+            r#type,
+            label,
+            data: data.into(),
+            index_axis: None,
+            parsing: None,
+            span_gaps: None,
+            style: DatasetStyle::default(),
+            datalabels: None,
+            elements: None,
+            value_colors: None
+        }
+    }
+
+    pub fn with_elements(mut self, elements: ElementsConfig) -> Self {
+        self.elements = Some(elements);
         self
     }
 
-    pub fn with_point_config(mut self, conf: PointConfig) -> Self{
-        self.point = Some(conf);
+    pub fn with_index_axis(mut self, axis: AxisName) -> Self {
+        self.index_axis = Some(axis);
+        self
+    }
+
+    pub fn with_span_gaps(mut self, span_gaps: SpanGaps) -> Self {
+        self.span_gaps = Some(span_gaps);
+        self
+    }
+
+    /// Value labels of this dataset, over the chart-level ones
+    pub fn with_data_labels(mut self, labels: DataLabels) -> Self {
+        self.datalabels = Some(labels);
         self
     }
 
+    /// Styling set directly on the dataset, it takes precedence over the chart `elements` config
+    pub fn with_style(mut self, style: DatasetStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Colors each point by its value on `scale`, `values` go in the same order as the data points.
+    ///
+    /// Fails when there isn't one value per point. Colors of points left out by `NonFinitePolicy::Skip` are left out with them.
+    pub fn with_value_colors(mut self, values: &[f64], scale: &ColorScale) -> Result<Self, ValueColorsError> {
+        let points = self.data.finite_points().len();
+        if values.len() != points {
+            return Err(ValueColorsError { points, values: values.len() });
+        }
+        self.value_colors = Some(scale.colors_for(values));
+        Ok(self)
+    }
+
+    fn apply_value_colors(&mut self, non_finite: NonFinitePolicy) {
+        let Some(mut colors) = self.value_colors.take() else {
+            return;
+        };
+        if non_finite == NonFinitePolicy::Skip {
+            let mut finite = self.data.finite_points().into_iter();
+            colors.retain(|_| finite.next().unwrap_or(true));
+        }
+        //line-based charts fill the area with the background color, their points have their own
+        if !matches!(self.r#type, ChartType::Line | ChartType::Scatter | ChartType::Radar) {
+            self.style.background_color = Some(Paint::PerPoint(colors.clone()));
+        }
+        self.style.point_border_color = Some(Paint::PerPoint(colors.clone()));
+        self.style.point_background_color = Some(Paint::PerPoint(colors));
+    }
+
+    fn apply_auto_color(&mut self, color: Rgb, background_alpha: f32) {
+        if self.style.background_color.is_none() {
+            self.style.background_color = Some(color.with_alpha(background_alpha).into());
+        }
+        if self.style.border_color.is_none() {
+            self.style.border_color = Some(color.into());
+        }
+    }
 }
 
-#[derive(Serialize, Debug, Clone)]
-#[serde(rename_all = "lowercase")]
-pub enum CubicInterpolationMode{
-    Default,
-    Monotone
+/// Whether a line is drawn over missing (`null`) values
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(untagged)]
+pub enum SpanGaps {
+    Bool(bool),
+    ///only gaps up to this distance on the index axis are spanned, in milliseconds for time scales
+    MaxGap(f64)
 }
 
-#[derive(Serialize, Debug, Clone,Default)]
-pub struct LineConfig {
-    ///how much bezier rounding to use, default is 0 - no bezier
+/// Dataset-level styling, properties only used by other chart types are ignored by Chart.js
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DatasetStyle {
     #[serde(skip_serializing_if = "Option::is_none")]
-    tension: Option<f32>,
-
+    border_color: Option<Paint>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    cubic_interpolation_mode: Option<CubicInterpolationMode>,
+    background_color: Option<Paint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_width: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hover_border_color: Option<Paint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hover_background_color: Option<Paint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hover_border_width: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hidden: Option<bool>,
+    ///drawing order, datasets with a lower order are drawn on top
+    #[serde(skip_serializing_if = "Option::is_none")]
+    order: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    clip: Option<Clip>,
 
+    //line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_dash: Option<Vec<f32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_dash_offset: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_cap_style: Option<CapStyle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_join_style: Option<JoinStyle>,
     #[serde(skip_serializing_if = "Option::is_none")]
     fill: Option<Fill>,
-
     #[serde(skip_serializing_if = "Option::is_none")]
-    border_color: Option<Rgb>,
+    tension: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stepped: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cubic_interpolation_mode: Option<CubicInterpolationMode>,
+    ///false draws the points only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    show_line: Option<bool>,
 
+    //point
+    #[serde(skip_serializing_if = "Option::is_none")]
+    point_radius: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    background_color: Option<Rgb>,
+    point_style: Option<PointStyle>,
+    ///point rotation in degrees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    point_rotation: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    point_border_color: Option<Paint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    point_background_color: Option<Paint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    point_border_width: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    point_hover_radius: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    point_hit_radius: Option<f32>,
 
-    stepped: bool
+    //bar
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bar_percentage: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category_percentage: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bar_thickness: Option<BarThickness>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_bar_thickness: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_radius: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_skipped: Option<BorderSkipped>,
+    ///datasets with the same stack name are stacked on each other, when the scale is stacked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stack: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grouped: Option<bool>,
 
+    //arc (pie, doughnut, polar area)
+    ///arc offset in pixels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hover_offset: Option<f32>,
+    ///gap between arcs in pixels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spacing: Option<f32>,
+    ///sweep of the arcs in degrees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    circumference: Option<f32>,
+    ///starting angle in degrees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rotation: Option<f32>,
+    ///relative thickness of the dataset ring
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_align: Option<BorderAlign>
 }
 
-impl LineConfig {
+impl DatasetStyle {
 
-    pub fn with_tension(mut self, tension: f32) -> Self{
-        self.tension = Some(tension);
-        self.stepped = false;
+    pub fn with_border_color<C: Into<Paint>>(mut self, color: C) -> Self {
+        self.border_color = Some(color.into());
         self
     }
 
-    pub fn with_stepped(mut self, stepped: bool) -> Self{
-        self.stepped = stepped;
-        self.tension = None;
+    pub fn with_background_color<C: Into<Paint>>(mut self, color: C) -> Self {
+        self.background_color = Some(color.into());
         self
     }
 
-    pub fn with_cubic_interpolation_mode(mut self, mode: CubicInterpolationMode) -> Self{
-        self.cubic_interpolation_mode = Some(mode);
+    pub fn with_border_width(mut self, width: f32) -> Self {
+        self.border_width = Some(width);
         self
     }
 
-    pub fn with_fill(mut self, fill: Fill) -> Self{
-        self.fill = Some(fill);
+    pub fn with_hover_border_color<C: Into<Paint>>(mut self, color: C) -> Self {
+        self.hover_border_color = Some(color.into());
         self
     }
 
-    pub fn with_border_color(mut self, color: Rgb) -> Self{
-        self.border_color = Some(color);
+    pub fn with_hover_background_color<C: Into<Paint>>(mut self, color: C) -> Self {
+        self.hover_background_color = Some(color.into());
         self
     }
 
-    pub fn with_background_color(mut self, color: Rgb) -> Self{
-        self.background_color = Some(color);
+    pub fn with_hover_border_width(mut self, width: f32) -> Self {
+        self.hover_border_width = Some(width);
         self
     }
-}
 
+    pub fn with_hidden(mut self, hidden: bool) -> Self {
+        self.hidden = Some(hidden);
+        self
+    }
 
-#[derive(Serialize, Debug, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct PointConfig{
+    pub fn with_order(mut self, order: i32) -> Self {
+        self.order = Some(order);
+        self
+    }
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    border_color: Option<Rgb>,
+    pub fn with_clip(mut self, clip: Clip) -> Self {
+        self.clip = Some(clip);
+        self
+    }
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    background_color: Option<Rgb>,
+    ///lengths of alternating dashes and gaps in pixels
+    pub fn with_border_dash(mut self, dash: Vec<f32>) -> Self {
+        self.border_dash = Some(dash);
+        self
+    }
+
+    pub fn with_border_dash_offset(mut self, offset: f32) -> Self {
+        self.border_dash_offset = Some(offset);
+        self
+    }
+
+    pub fn with_border_cap_style(mut self, style: CapStyle) -> Self {
+        self.border_cap_style = Some(style);
+        self
+    }
+
+    pub fn with_border_join_style(mut self, style: JoinStyle) -> Self {
+        self.border_join_style = Some(style);
+        self
+    }
+
+    pub fn with_fill(mut self, fill: Fill) -> Self {
+        self.fill = Some(fill);
+        self
+    }
+
+    pub fn with_tension(mut self, tension: f32) -> Self {
+        self.tension = Some(tension);
+        self
+    }
+
+    pub fn with_stepped(mut self, stepped: bool) -> Self {
+        self.stepped = Some(stepped);
+        self
+    }
+
+    pub fn with_cubic_interpolation_mode(mut self, mode: CubicInterpolationMode) -> Self {
+        self.cubic_interpolation_mode = Some(mode);
+        self
+    }
+
+    pub fn with_show_line(mut self, show: bool) -> Self {
+        self.show_line = Some(show);
+        self
+    }
+
+    pub fn with_point_radius(mut self, radius: f32) -> Self {
+        self.point_radius = Some(radius);
+        self
+    }
+
+    pub fn with_point_style(mut self, style: PointStyle) -> Self {
+        self.point_style = Some(style);
+        self
+    }
+
+    pub fn with_point_rotation(mut self, rotation: f32) -> Self {
+        self.point_rotation = Some(rotation);
+        self
+    }
+
+    pub fn with_point_border_color<C: Into<Paint>>(mut self, color: C) -> Self {
+        self.point_border_color = Some(color.into());
+        self
+    }
+
+    pub fn with_point_background_color<C: Into<Paint>>(mut self, color: C) -> Self {
+        self.point_background_color = Some(color.into());
+        self
+    }
+
+    pub fn with_point_border_width(mut self, width: f32) -> Self {
+        self.point_border_width = Some(width);
+        self
+    }
+
+    pub fn with_point_hover_radius(mut self, radius: f32) -> Self {
+        self.point_hover_radius = Some(radius);
+        self
+    }
+
+    pub fn with_point_hit_radius(mut self, radius: f32) -> Self {
+        self.point_hit_radius = Some(radius);
+        self
+    }
+
+    ///share of the category width a bar takes, from 0 to 1
+    pub fn with_bar_percentage(mut self, percentage: f32) -> Self {
+        self.bar_percentage = Some(percentage);
+        self
+    }
+
+    ///share of the available width a category takes, from 0 to 1
+    pub fn with_category_percentage(mut self, percentage: f32) -> Self {
+        self.category_percentage = Some(percentage);
+        self
+    }
+
+    pub fn with_bar_thickness(mut self, thickness: BarThickness) -> Self {
+        self.bar_thickness = Some(thickness);
+        self
+    }
+
+    pub fn with_max_bar_thickness(mut self, thickness: f32) -> Self {
+        self.max_bar_thickness = Some(thickness);
+        self
+    }
+
+    pub fn with_border_radius(mut self, radius: f32) -> Self {
+        self.border_radius = Some(radius);
+        self
+    }
+
+    pub fn with_border_skipped(mut self, skipped: BorderSkipped) -> Self {
+        self.border_skipped = Some(skipped);
+        self
+    }
+
+    pub fn with_stack(mut self, stack: &str) -> Self {
+        self.stack = Some(stack.to_string());
+        self
+    }
+
+    pub fn with_grouped(mut self, grouped: bool) -> Self {
+        self.grouped = Some(grouped);
+        self
+    }
+
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub fn with_hover_offset(mut self, offset: f32) -> Self {
+        self.hover_offset = Some(offset);
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = Some(spacing);
+        self
+    }
+
+    pub fn with_circumference(mut self, degrees: f32) -> Self {
+        self.circumference = Some(degrees);
+        self
+    }
+
+    pub fn with_rotation(mut self, degrees: f32) -> Self {
+        self.rotation = Some(degrees);
+        self
+    }
+
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    pub fn with_border_align(mut self, align: BorderAlign) -> Self {
+        self.border_align = Some(align);
+        self
+    }
+}
+
+/// How far outside of the chart area a dataset is drawn
+#[derive(Debug, Clone, PartialEq)]
+pub enum Clip {
+    ///same margin in pixels on all sides, negative values clip inside of the chart area
+    Pixels(f32),
+    Sides { left: f32, top: f32, right: f32, bottom: f32 },
+    ///not clipped at all
+    Disabled
+}
+
+impl Serialize for Clip {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        match self {
+            Clip::Pixels(px) => serializer.serialize_f32(*px),
+            Clip::Sides { left, top, right, bottom } => {
+                let mut map = serializer.serialize_map(Some(4))?;
+                map.serialize_entry("left", left)?;
+                map.serialize_entry("top", top)?;
+                map.serialize_entry("right", right)?;
+                map.serialize_entry("bottom", bottom)?;
+                map.end()
+            }
+            Clip::Disabled => serializer.serialize_bool(false)
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CapStyle {
+    Butt,
+    Round,
+    Square
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum JoinStyle {
+    Bevel,
+    Round,
+    Miter
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BarThickness {
+    Pixels(f32),
+    ///bars take all the space available, with no gaps
+    Flex
+}
+
+impl Serialize for BarThickness {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        match self {
+            BarThickness::Pixels(px) => serializer.serialize_f32(*px),
+            BarThickness::Flex => serializer.serialize_str("flex")
+        }
+    }
+}
+
+/// Edge of a bar drawn without a border
+#[derive(Debug, Clone, PartialEq)]
+pub enum BorderSkipped {
+    Start,
+    End,
+    Middle,
+    Bottom,
+    Left,
+    Top,
+    Right,
+    ///all edges have a border
+    None
+}
+
+impl Serialize for BorderSkipped {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        let edge = match self {
+            BorderSkipped::Start => "start",
+            BorderSkipped::End => "end",
+            BorderSkipped::Middle => "middle",
+            BorderSkipped::Bottom => "bottom",
+            BorderSkipped::Left => "left",
+            BorderSkipped::Top => "top",
+            BorderSkipped::Right => "right",
+            BorderSkipped::None => return serializer.serialize_bool(false)
+        };
+        serializer.serialize_str(edge)
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum BorderAlign {
+    Center,
+    Inner
+}
+
+#[derive(Serialize, Debug, Clone,Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ElementsConfig{
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line:  Option<LineConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    point: Option<PointConfig>
+
+}
+
+impl ElementsConfig {
+
+    pub fn with_line_config(mut self, conf: LineConfig) -> Self{
+        self.line = Some(conf);
+        self
+    }
+
+    pub fn with_point_config(mut self, conf: PointConfig) -> Self{
+        self.point = Some(conf);
+        self
+    }
+
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum CubicInterpolationMode{
+    Default,
+    Monotone
+}
+
+#[derive(Serialize, Debug, Clone,Default)]
+pub struct LineConfig {
+    ///how much bezier rounding to use, default is 0 - no bezier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tension: Option<f32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cubic_interpolation_mode: Option<CubicInterpolationMode>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fill: Option<Fill>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_color: Option<Paint>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background_color: Option<Paint>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    span_gaps: Option<SpanGaps>,
+
+    stepped: bool
+
+}
+
+impl LineConfig {
+
+    pub fn with_tension(mut self, tension: f32) -> Self{
+        self.tension = Some(tension);
+        self.stepped = false;
+        self
+    }
+
+    pub fn with_stepped(mut self, stepped: bool) -> Self{
+        self.stepped = stepped;
+        self.tension = None;
+        self
+    }
+
+    pub fn with_cubic_interpolation_mode(mut self, mode: CubicInterpolationMode) -> Self{
+        self.cubic_interpolation_mode = Some(mode);
+        self
+    }
+
+    pub fn with_span_gaps(mut self, span_gaps: SpanGaps) -> Self{
+        self.span_gaps = Some(span_gaps);
+        self
+    }
+
+    pub fn with_fill(mut self, fill: Fill) -> Self{
+        self.fill = Some(fill);
+        self
+    }
+
+    pub fn with_border_color<C: Into<Paint>>(mut self, color: C) -> Self{
+        self.border_color = Some(color.into());
+        self
+    }
+
+    pub fn with_background_color<C: Into<Paint>>(mut self, color: C) -> Self{
+        self.background_color = Some(color.into());
+        self
+    }
+}
+
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PointConfig{
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_color: Option<Paint>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background_color: Option<Paint>,
 
     point_style: PointStyle,
 
@@ -423,12 +1261,12 @@ impl PointConfig {
         self.rotation = rotation;
         self
     }
-    pub fn with_border_color(mut self, color: Rgb) -> Self{
-        self.border_color = Some(color);
+    pub fn with_border_color<C: Into<Paint>>(mut self, color: C) -> Self{
+        self.border_color = Some(color.into());
         self
     }
-    pub fn with_background_color(mut self, color: Rgb) -> Self{
-        self.background_color = Some(color);
+    pub fn with_background_color<C: Into<Paint>>(mut self, color: C) -> Self{
+        self.background_color = Some(color.into());
         self
     }
 
@@ -452,7 +1290,11 @@ impl PointConfig {
 }
 
 
+/// Shape of points, serialized as the Chart.js names (`circle`, `crossRot`, `rectRounded`, ...).
+///
+/// Earlier versions sent the variant names as they are (`CrossRot`), which Chart.js doesn't recognize.
 #[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
 pub enum PointStyle{
     Circle,
     Cross,
@@ -495,6 +1337,7 @@ pub enum AxisValueVariant{
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
 pub enum Boundary{
     Start,
     End,
@@ -503,165 +1346,923 @@ pub enum Boundary{
     Shape
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
-struct  Fill {
+pub struct Fill {
     target: FillVariant,
 
+    above: Option<Paint>,
+
+    below: Option<Paint>
+}
+
+#[derive(Serialize)]
+struct FillJs<'a> {
+    target: &'a FillVariant,
+
     #[serde(skip_serializing_if = "Option::is_none")]
-    above: Option<Rgb>,
+    above: Option<&'a Paint>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    below: Option<Rgb>
+    below: Option<&'a Paint>
+}
+
+/// Gradients need the chart area, a fill with them is resolved for every update by a scriptable `fill`
+impl Serialize for Fill {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let fill = FillJs{ target: &self.target, above: self.above.as_ref(), below: self.below.as_ref() };
+        let is_gradient = |paint: &Option<Paint>| matches!(paint, Some(Paint::Gradient(_)));
+        if !is_gradient(&self.above) && !is_gradient(&self.below) {
+            return fill.serialize(serializer);
+        }
+        serialize_raw(
+            &format!(
+                "function(context){{ const f = {}; \
+                 if (typeof f.above === 'function') {{ f.above = f.above(context); }} \
+                 if (typeof f.below === 'function') {{ f.below = f.below(context); }} return f; }}",
+                to_js(&fill).map_err(serde::ser::Error::custom)?
+            ),
+            serializer
+        )
+    }
 }
 
 
 impl Fill {
 
+    pub fn new(target: FillVariant) -> Self{
+        Self{
+            target,
+            above: None,
+            below: None
+        }
+    }
+
     pub fn with_target(mut self, target: FillVariant) -> Self{
         self.target = target;
         self
     }
-    pub fn with_above(mut self, color: Rgb) -> Self{
-        self.above = Some(color);
+    pub fn with_above<C: Into<Paint>>(mut self, color: C) -> Self{
+        self.above = Some(color.into());
         self
     }
 
-    pub fn with_below(mut self, color: Rgb) -> Self{
-        self.below = Some(color);
+    pub fn with_below<C: Into<Paint>>(mut self, color: C) -> Self{
+        self.below = Some(color.into());
         self
     }
 
 }
 
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 #[serde(rename_all = "lowercase")]
-enum AxisName{
+pub enum AxisName{
     X,
     Y
 }
 
+/// Remaps the object keys Chart.js reads the values from
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Parsing{
+    x_axis_key: AxisName,
+    y_axis_key: AxisName
+}
+
 #[derive(Debug, Clone)]
 pub struct ChartOptions<X,Y> where X:WithTypeAndSerializer, Y:WithTypeAndSerializer{
     pub(crate) scales: Option<ScalingConfig<X,Y>>,
+    pub(crate) index_axis: Option<AxisName>,
+    pub(crate) time_zone: Option<ChartTimeZone>,
+    pub(crate) naive_time_zone: Option<ChartTimeZone>,
+    #[cfg(feature = "decimal")]
+    pub(crate) decimal_rounding: Option<DecimalRounding>,
+    pub(crate) non_finite: NonFinitePolicy,
+    pub(crate) auto_colors: Option<AutoColors>,
+    pub(crate) color_legend: Option<ColorLegend>,
     pub(crate) aspect_ratio: Option<f32>,
     pub(crate) elements: Option<ElementsConfig>,
+    pub(crate) on_click: Option<ClickConfig>,
     pub(crate) plugins: Plugins,
+    ///options of plugins with values typed like the data, under `plugins` in the output
+    pub(crate) annotation: Option<Annotations<X,Y>>,
+    pub(crate) zoom: Option<ZoomConfig<X,Y>>,
 }
 
 impl<X,Y> Default for ChartOptions<X,Y> where X:WithTypeAndSerializer, Y:WithTypeAndSerializer{
     fn default() -> Self {
         ChartOptions{
             scales: None,
+            index_axis: None,
+            time_zone: None,
+            naive_time_zone: None,
+            #[cfg(feature = "decimal")]
+            decimal_rounding: None,
+            non_finite: NonFinitePolicy::default(),
+            auto_colors: None,
+            color_legend: None,
             aspect_ratio: None,
             plugins: Plugins::default(),
-            elements: None
+            annotation: None,
+            zoom: None,
+            elements: None,
+            on_click: None
         }
     }
 }
 
-#[derive(Serialize, Debug, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct ScalingConfig<X,Y> where X:WithTypeAndSerializer, Y:WithTypeAndSerializer{
-    #[serde(skip_serializing_if = "Option::is_none")]
-    x: Option<ScaleConfig<X>>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
+impl Parsing{
+
+    /// Data points are always `{x: index, y: value}`, for the `y` index axis
+    /// Chart.js is told to read them the other way around.
+    fn for_index_axis(axis: Option<AxisName>) -> Option<Parsing> {
+        match axis {
+            Some(AxisName::Y) => Some(Parsing{
+                x_axis_key: AxisName::Y,
+                y_axis_key: AxisName::X
+            }),
+            _ => None
+        }
+    }
+}
+
+impl<X,Y> ChartOptions<X,Y> where X:WithTypeAndSerializer, Y:WithTypeAndSerializer{
+
+    pub(crate) fn serialize_settings(&self) -> SerializeSettings {
+        SerializeSettings{
+            //naive date-times are in the naive zone if set, then in the display zone, UTC otherwise
+            #[cfg(any(feature = "time_axis", feature = "chrono_axis", feature = "jiff_axis"))]
+            naive_zone: self.naive_time_zone
+                .or(self.time_zone)
+                .unwrap_or(ChartTimeZone::Utc),
+            non_finite: self.non_finite,
+            transposed: self.index_axis == Some(AxisName::Y),
+            #[cfg(feature = "decimal")]
+            decimal_rounding: self.decimal_rounding
+        }
+    }
+
+    fn apply_time_zone(&mut self) {
+        if let (Some(zone), Some(scales)) = (&self.time_zone, &mut self.scales) {
+            if let Some(x) = &mut scales.x {
+                x.set_time_zone(zone);
+            }
+            if let Some(y) = &mut scales.y {
+                y.set_time_zone(zone);
+            }
+        }
+    }
+
+    /// Time valued axes are time scales unless configured otherwise
+    pub(crate) fn uses_time_scale(&self) -> bool {
+        let x = self.scales.as_ref().and_then(|s| s.x.as_ref()).and_then(|x| x.r#type.clone()).unwrap_or_else(X::scale_type);
+        let y = self.scales.as_ref().and_then(|s| s.y.as_ref()).and_then(|y| y.r#type.clone()).unwrap_or_else(Y::scale_type);
+        [x, y].iter().any(|t| matches!(t, ScaleType::Time | ScaleType::TimeSeries))
+    }
+
+    pub(crate) fn oriented_scales(&self) -> Option<OrientedScales<'_,X,Y>> {
+        self.scales.as_ref().map(|scales| OrientedScales{
+            scales,
+            transposed: self.index_axis == Some(AxisName::Y)
+        })
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScalingConfig<X,Y> where X:WithTypeAndSerializer, Y:WithTypeAndSerializer{
+    #[serde(skip_serializing_if = "Option::is_none")]
+    x: Option<ScaleConfig<X>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
     y: Option<ScaleConfig<Y>>
 }
 
+/// Scales as Chart.js sees them: with the `y` index axis the `X` scale is rendered vertically
+pub(crate) struct OrientedScales<'a,X,Y> where X:WithTypeAndSerializer, Y:WithTypeAndSerializer{
+    scales: &'a ScalingConfig<X,Y>,
+    transposed: bool
+}
+
+impl<X,Y> Serialize for OrientedScales<'_,X,Y> where X:WithTypeAndSerializer+Serialize, Y:WithTypeAndSerializer+Serialize{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        if !self.transposed {
+            return self.scales.serialize(serializer);
+        }
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(y) = &self.scales.y {
+            map.serialize_entry("x", y)?;
+        }
+        if let Some(x) = &self.scales.x {
+            map.serialize_entry("y", x)?;
+        }
+        map.end()
+    }
+}
+
 #[derive(Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ScaleConfig<T> where T:WithTypeAndSerializer{
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    r#type: Option<ScaleType>,
+    r#type: Option<ScaleType>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    labels: Option<Vec<ValueSerializeWrapper<T>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    align_to_pixels: Option<bool>,
+
+    reverse: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max: Option<ValueSerializeWrapper<T>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min: Option<ValueSerializeWrapper<T>>,
+
+    ///unlike min/max these are only used if data does not go beyond them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suggested_max: Option<ValueSerializeWrapper<T>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suggested_min: Option<ValueSerializeWrapper<T>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    begin_at_zero: Option<bool>,
+
+    ///extra space added above max and below min of the data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grace: Option<Grace>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bounds: Option<Bounds>,
+
+    ///extra space at both edges, labels are shifted to be between grid lines
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<bool>,
+
+    ///order of axes placed on the same side of the chart, higher is further from the chart area
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<i32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<AxisTitle>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ticks: Option<TickConfig>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time: Option<TimeScaleOptions>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    adapters: Option<AdaptersConfig>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grid: Option<GridConfig>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border: Option<BorderConfig>
+}
+
+
+impl<T> Default for ScaleConfig<T> where T:WithTypeAndSerializer{
+    fn default() -> Self {
+        ScaleConfig{
+            r#type: None,
+            labels: None,
+            align_to_pixels: None,
+            reverse: false,
+            max: None,
+            min: None,
+            suggested_max: None,
+            suggested_min: None,
+            begin_at_zero: None,
+            grace: None,
+            bounds: None,
+            offset: None,
+            weight: None,
+            title: None,
+            ticks: None,
+            time: None,
+            adapters: None,
+            grid: None,
+            border: None
+        }
+    }
+}
+
+impl<T> ScaleConfig<T> where T:WithTypeAndSerializer{
+
+    pub fn new_category(reverse:bool,values: Vec<T>) -> Self {
+        Self {
+            r#type: Some(ScaleType::Category),
+            labels: Some(values.into_iter().map(|v| v.into()).collect()),
+            reverse,
+            ..ScaleConfig::<T>::default()
+        }
+    }
+
+    pub fn with_type(mut self, r#type: ScaleType) -> Self {
+        self.r#type = Some(r#type);
+        self
+    }
+
+    pub fn with_align_to_pixels(mut self, align_to_pixels: bool) -> Self {
+        self.align_to_pixels = Some(align_to_pixels);
+        self
+    }
+
+    pub fn with_max(mut self, max: T) -> Self {
+        self.max = Some(max.into());
+        self
+    }
+
+    pub fn with_min(mut self, min: T) -> Self {
+        self.min = Some(min.into());
+        self
+    }
+
+    pub fn with_suggested_max(mut self, max: T) -> Self {
+        self.suggested_max = Some(max.into());
+        self
+    }
+
+    pub fn with_suggested_min(mut self, min: T) -> Self {
+        self.suggested_min = Some(min.into());
+        self
+    }
+
+    pub fn with_begin_at_zero(mut self, begin_at_zero: bool) -> Self {
+        self.begin_at_zero = Some(begin_at_zero);
+        self
+    }
+
+    pub fn with_grace(mut self, grace: Grace) -> Self {
+        self.grace = Some(grace);
+        self
+    }
+
+    pub fn with_bounds(mut self, bounds: Bounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    pub fn with_offset(mut self, offset: bool) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub fn with_weight(mut self, weight: i32) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    pub fn with_str_title(mut self, title: &str) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn with_title(mut self, title: AxisTitle) -> Self {
+        self.title = Some(title);
+        self
+    }
+
+    pub fn with_labels(mut self, labels: Vec<T>) -> Self {
+        self.labels = Some(labels.into_iter().map(|v| v.into()).collect());
+        self
+    }
+
+    pub fn with_reverse(mut self, reverse: bool) -> Self {
+        self.reverse = reverse;
+        self
+    }
+
+    pub fn with_ticks(mut self, ticks: TickConfig) -> Self {
+        self.ticks = Some(ticks);
+        self
+    }
+
+    /// Time scale settings, the distribution (if set) picks between `Time` and `TimeSeries` scale type
+    pub fn with_time(mut self, time: TimeScaleOptions) -> Self {
+        match time.distribution {
+            Some(TimeDistribution::Linear) => self.r#type = Some(ScaleType::Time),
+            Some(TimeDistribution::Series) => self.r#type = Some(ScaleType::TimeSeries),
+            None => {}
+        }
+        self.time = Some(time);
+        self
+    }
+
+    pub(crate) fn set_time_zone(&mut self, zone: &ChartTimeZone) {
+        if self.is_time_scale() {
+            if let Some(time) = &mut self.time {
+                time.luxon = true;
+            }
+            self.adapters = Some(AdaptersConfig{
+                date: DateAdapterConfig{
+                    zone: zone.luxon_zone()
+                }
+            });
+        }
+    }
+
+    pub(crate) fn is_time_scale(&self) -> bool {
+        matches!(self.r#type, Some(ScaleType::Time) | Some(ScaleType::TimeSeries))
+    }
+
+    pub fn with_grid(mut self, grid: GridConfig) -> Self {
+        self.grid = Some(grid);
+        self
+    }
+
+    pub fn with_border(mut self, border: BorderConfig) -> Self {
+        self.border = Some(border);
+        self
+    }
+
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TickConfig{
+    ///fixed distance between ticks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    step_size: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_ticks_limit: Option<u32>,
+
+    ///number of decimal places in tick values
+    #[serde(skip_serializing_if = "Option::is_none")]
+    precision: Option<u8>,
+
+    ///skip labels that would overlap
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auto_skip: Option<bool>,
+
+    ///label rotation in degrees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_rotation: Option<u16>,
+
+    ///label rotation in degrees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_rotation: Option<u16>,
+
+    ///padding between the tick label and the axis
+    #[serde(skip_serializing_if = "Option::is_none")]
+    padding: Option<u16>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<Color>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    font: Option<Font>,
+
+    ///add ticks at min and max even if they are not on a step
+    #[serde(skip_serializing_if = "Option::is_none")]
+    include_bounds: Option<bool>,
+
+    ///where time scale ticks come from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<TickSource>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    callback: Option<TickCallback>
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum TickSource{
+    ///optimal ticks based on the scale size and time options
+    Auto,
+    ///ticks at data points
+    Data,
+    ///ticks at the scale labels
+    Labels
+}
+
+impl TickConfig {
+
+    pub fn with_step_size(mut self, step_size: f64) -> Self {
+        self.step_size = Some(step_size);
+        self
+    }
+
+    pub fn with_max_ticks_limit(mut self, limit: u32) -> Self {
+        self.max_ticks_limit = Some(limit);
+        self
+    }
+
+    pub fn with_precision(mut self, precision: u8) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    pub fn with_auto_skip(mut self, auto_skip: bool) -> Self {
+        self.auto_skip = Some(auto_skip);
+        self
+    }
+
+    pub fn with_max_rotation(mut self, rotation: u16) -> Self {
+        self.max_rotation = Some(rotation);
+        self
+    }
+
+    pub fn with_min_rotation(mut self, rotation: u16) -> Self {
+        self.min_rotation = Some(rotation);
+        self
+    }
+
+    pub fn with_padding(mut self, padding: u16) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    pub fn with_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    pub fn with_include_bounds(mut self, include_bounds: bool) -> Self {
+        self.include_bounds = Some(include_bounds);
+        self
+    }
+
+    pub fn with_source(mut self, source: TickSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    ///format tick labels of a numeric scale
+    pub fn with_format(mut self, format: NumberFormat) -> Self {
+        self.callback = Some(TickCallback::Format(format));
+        self
+    }
+
+    ///custom `function(value, index, ticks)` returning the label
+    pub fn with_callback(mut self, callback: JsExpr) -> Self {
+        self.callback = Some(TickCallback::Custom(callback));
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeUnit{
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct AdaptersConfig{
+    date: DateAdapterConfig
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct DateAdapterConfig{
+    zone: String
+}
+
+/// How data points are spread over a time axis
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeDistribution{
+    ///proportionally to the time between points, `time` scale
+    Linear,
+    ///same distance between every point, `timeseries` scale
+    Series
+}
+
+/// Options of time scales, date formats are in the syntax of the date-fns adapter.
+///
+/// When the chart has a time zone they are translated to luxon's, formats luxon can't express fail to render.
+#[derive(Debug, Clone, Default)]
+pub struct TimeScaleOptions{
+    ///fixed unit of the ticks, picked automatically when not set
+    unit: Option<TimeUnit>,
+
+    ///smallest unit to pick automatically
+    min_unit: Option<TimeUnit>,
+
+    ///values are rounded to the start of this unit
+    round: Option<TimeUnit>,
+
+    ///tick label format per unit
+    display_formats: BTreeMap<TimeUnit, String>,
+
+    ///format of the tooltip title
+    tooltip_format: Option<String>,
+
+    ///weeks start on monday
+    iso_weekday: Option<bool>,
+
+    distribution: Option<TimeDistribution>,
+
+    ///formats go to the luxon adapter, see `ChartConfig::with_time_zone`
+    luxon: bool
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TimeScaleOptionsJs<'a>{
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unit: Option<TimeUnit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_unit: Option<TimeUnit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    round: Option<TimeUnit>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    display_formats: BTreeMap<TimeUnit, Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tooltip_format: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    iso_weekday: Option<bool>
+}
+
+impl Serialize for TimeScaleOptions {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        TimeScaleOptionsJs{
+            unit: self.unit,
+            min_unit: self.min_unit,
+            round: self.round,
+            display_formats: self.display_formats.iter()
+                .map(|(unit, format)| self.adapter_format(format).map(|format| (*unit, format)))
+                .collect::<Result<_, _>>()
+                .map_err(serde::ser::Error::custom)?,
+            tooltip_format: self.tooltip_format.as_deref()
+                .map(|format| self.adapter_format(format))
+                .transpose()
+                .map_err(serde::ser::Error::custom)?,
+            iso_weekday: self.iso_weekday
+        }.serialize(serializer)
+    }
+}
+
+impl TimeScaleOptions{
+
+    /// Date format in the syntax of the date adapter in use
+    fn adapter_format<'a>(&self, format: &'a str) -> Result<Cow<'a, str>, String> {
+        if self.luxon {
+            luxon_format(format).map(Cow::Owned)
+        } else {
+            Ok(Cow::Borrowed(format))
+        }
+    }
+
+    pub fn with_unit(mut self, unit: TimeUnit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    pub fn with_min_unit(mut self, unit: TimeUnit) -> Self {
+        self.min_unit = Some(unit);
+        self
+    }
+
+    pub fn with_round(mut self, unit: TimeUnit) -> Self {
+        self.round = Some(unit);
+        self
+    }
+
+    pub fn with_display_format(mut self, unit: TimeUnit, format: &str) -> Self {
+        self.display_formats.insert(unit, format.to_string());
+        self
+    }
+
+    pub fn with_tooltip_format(mut self, format: &str) -> Self {
+        self.tooltip_format = Some(format.to_string());
+        self
+    }
+
+    pub fn with_iso_weekday(mut self, iso_weekday: bool) -> Self {
+        self.iso_weekday = Some(iso_weekday);
+        self
+    }
+
+    pub fn with_distribution(mut self, distribution: TimeDistribution) -> Self {
+        self.distribution = Some(distribution);
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum Grace{
+    ///in axis units
+    Absolute(f64),
+    ///relative to the data range
+    Percent(Percent)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum Bounds{
+    ///makes sure data is fully visible, labels outside are removed
+    Data,
+    ///makes sure ticks are fully visible, data outside is truncated
+    Ticks
+}
+
+/// Option value that can change from one tick to another
+#[derive(Debug, Clone)]
+pub enum PerTick<T: Serialize>{
+    ///same value for every tick
+    Fixed(T),
+    ///`zero` for the tick at value 0, `other` for the rest, e.g. to highlight the zero line
+    ZeroLine{ zero: T, other: T },
+    ///custom scriptable `function(context)`, see Chart.js scriptable options
+    Script(JsExpr)
+}
+
+impl<T: Serialize> From<T> for PerTick<T> {
+    fn from(value: T) -> Self {
+        PerTick::Fixed(value)
+    }
+}
+
+impl From<Rgb> for PerTick<Color> {
+    fn from(value: Rgb) -> Self {
+        PerTick::Fixed(value.into())
+    }
+}
+
+impl<T: Serialize> Serialize for PerTick<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        match self {
+            PerTick::Fixed(value) => value.serialize(serializer),
+            PerTick::ZeroLine { zero, other } => serialize_raw(
+                &format!(
+                    "function(context){{ return context.tick && context.tick.value === 0 ? {} : {}; }}",
+                    js_literal(zero),
+                    js_literal(other)
+                ),
+                serializer
+            ),
+            PerTick::Script(expr) => expr.serialize(serializer)
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GridConfig{
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<PerTick<Color>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    labels: Option<Vec<ValueSerializeWrapper<T>>>,
+    line_width: Option<PerTick<f32>>,
 
+    ///if false, grid lines are drawn only next to the axis, not over the chart area
     #[serde(skip_serializing_if = "Option::is_none")]
-    align_to_pixels: Option<bool>,
+    draw_on_chart_area: Option<bool>,
 
-    reverse: bool,
+    ///draw the short tick marks between the axis and the labels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    draw_ticks: Option<bool>,
 
+    ///length of the tick marks in px
     #[serde(skip_serializing_if = "Option::is_none")]
-    max: Option<ValueSerializeWrapper<T>>,
+    tick_length: Option<u16>,
 
+    ///shift grid lines to be between labels, default for bar charts
     #[serde(skip_serializing_if = "Option::is_none")]
-    min: Option<ValueSerializeWrapper<T>>,
+    offset: Option<bool>,
 
+    ///drawing order, grid is drawn under datasets when <= 0
     #[serde(skip_serializing_if = "Option::is_none")]
-    title: Option<AxisTitle>
+    z: Option<i32>
 }
 
+impl GridConfig {
 
-impl<T> Default for ScaleConfig<T> where T:WithTypeAndSerializer{
-    fn default() -> Self {
-        ScaleConfig{
-            r#type: None,
-            labels: None,
-            align_to_pixels: None,
-            reverse: false,
-            max: None,
-            min: None,
-            title: None
-        }
+    pub fn with_display(mut self, display: bool) -> Self {
+        self.display = Some(display);
+        self
     }
-}
 
-impl<T> ScaleConfig<T> where T:WithTypeAndSerializer{
+    pub fn with_color<C: Into<PerTick<Color>>>(mut self, color: C) -> Self {
+        self.color = Some(color.into());
+        self
+    }
 
-    pub fn new_category(reverse:bool,values: Vec<T>) -> Self {
-        Self {
-            r#type: Some(ScaleType::Category),
-            labels: Some(values.into_iter().map(|v| v.into()).collect()),
-            reverse,
-            ..ScaleConfig::<T>::default()
-        }
+    pub fn with_line_width<W: Into<PerTick<f32>>>(mut self, width: W) -> Self {
+        self.line_width = Some(width.into());
+        self
     }
 
-    pub fn with_type(mut self, r#type: ScaleType) -> Self {
-        self.r#type = Some(r#type);
+    pub fn with_draw_on_chart_area(mut self, draw: bool) -> Self {
+        self.draw_on_chart_area = Some(draw);
         self
     }
 
-    pub fn with_align_to_pixels(mut self, align_to_pixels: bool) -> Self {
-        self.align_to_pixels = Some(align_to_pixels);
+    pub fn with_draw_ticks(mut self, draw: bool) -> Self {
+        self.draw_ticks = Some(draw);
         self
     }
 
-    pub fn with_max(mut self, max: T) -> Self {
-        self.max = Some(max.into());
+    pub fn with_tick_length(mut self, length: u16) -> Self {
+        self.tick_length = Some(length);
         self
     }
 
-    pub fn with_min(mut self, min: T) -> Self {
-        self.min = Some(min.into());
+    pub fn with_offset(mut self, offset: bool) -> Self {
+        self.offset = Some(offset);
         self
     }
 
-    pub fn with_str_title(mut self, title: &str) -> Self {
-        self.title = Some(title.into());
+    pub fn with_z(mut self, z: i32) -> Self {
+        self.z = Some(z);
         self
     }
+}
 
-    pub fn with_title(mut self, title: AxisTitle) -> Self {
-        self.title = Some(title);
+/// Axis border, its `dash` also applies to the grid lines
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BorderConfig{
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<Paint>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    width: Option<f32>,
+
+    ///length and spacing of dashes, in px
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dash: Option<Vec<f32>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dash_offset: Option<f32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    z: Option<i32>
+}
+
+impl BorderConfig {
+
+    pub fn with_display(mut self, display: bool) -> Self {
+        self.display = Some(display);
         self
     }
 
-    pub fn with_labels(mut self, labels: Vec<T>) -> Self {
-        self.labels = Some(labels.into_iter().map(|v| v.into()).collect());
+    pub fn with_color<C: Into<Paint>>(mut self, color: C) -> Self {
+        self.color = Some(color.into());
         self
     }
 
-    pub fn with_reverse(mut self, reverse: bool) -> Self {
-        self.reverse = reverse;
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn with_dash(mut self, dash: Vec<f32>) -> Self {
+        self.dash = Some(dash);
+        self
+    }
+
+    pub fn with_dash_offset(mut self, offset: f32) -> Self {
+        self.dash_offset = Some(offset);
+        self
+    }
+
+    pub fn with_z(mut self, z: i32) -> Self {
+        self.z = Some(z);
         self
     }
+}
+
+#[derive(Debug, Clone)]
+pub enum TickCallback{
+    Format(NumberFormat),
+    Custom(JsExpr)
+}
 
+impl Serialize for TickCallback {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        match self {
+            TickCallback::Format(format) =>
+                serialize_raw(&format!("function(value){{ {} }}", format.js_body("value")), serializer),
+            TickCallback::Custom(expr) => expr.serialize(serializer)
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone,PartialEq)]
@@ -735,7 +2336,9 @@ pub struct Plugins{
     pub(crate) title: Option<Title>,
     pub(crate) subtitle: Option<Title>,
     pub(crate) legend: Option<Legend>,
-    pub(crate) tooltip: Option<Tooltip>
+    pub(crate) tooltip: Option<Tooltip>,
+    pub(crate) datalabels: Option<DataLabels>,
+    pub(crate) custom: Vec<AttachedPlugin>
 }
 
 impl Default for Plugins{
@@ -745,6 +2348,8 @@ impl Default for Plugins{
             subtitle: None,
             legend: None,
             tooltip: Some(Tooltip::default()),
+            datalabels: None,
+            custom: vec![]
         }
     }
 }
@@ -867,6 +2472,7 @@ impl Title{
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(text: &str) -> Self {
         Self{
             display: true,
@@ -911,13 +2517,75 @@ impl Title{
 
 
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Which items the tooltip shows, `X` and `Y` go by value type like `ZoomMode`,
+/// `X` is the direction of the `X` values even when it is vertical
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum TooltipMode{
+    ///placement rather than a mode, kept as the Chart.js default mode with the tooltip at the average position
+    #[deprecated(note = "use `Tooltip::with_position(TooltipPosition::Average)`")]
     Average,
-    Nearest
+    ///items at the same index in every dataset
+    Index,
+    ///all items of the dataset
+    Dataset,
+    ///items under the pointer
+    Point,
+    ///item closest to the pointer
+    Nearest,
+    ///items at the same position along the `X` values
+    X,
+    ///items at the same position along the `Y` values
+    Y
+}
+
+impl Serialize for TooltipMode {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let mode = match (self, settings().transposed) {
+            (TooltipMode::Average, _) => "nearest",
+            (TooltipMode::Index, _) => "index",
+            (TooltipMode::Dataset, _) => "dataset",
+            (TooltipMode::Point, _) => "point",
+            (TooltipMode::Nearest, _) => "nearest",
+            (TooltipMode::X, false) | (TooltipMode::Y, true) => "x",
+            (TooltipMode::Y, false) | (TooltipMode::X, true) => "y"
+        };
+        serializer.serialize_str(mode)
+    }
+}
+
+/// Directions the distance to the pointer is measured in, `X` and `Y` go by value type like `TooltipMode`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InteractionAxis{
+    X,
+    Y,
+    XY,
+    ///radial distance, for polar area and radar charts
+    R
+}
+
+impl Serialize for InteractionAxis {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let axis = match (self, settings().transposed) {
+            (InteractionAxis::XY, _) => "xy",
+            (InteractionAxis::R, _) => "r",
+            (InteractionAxis::X, false) | (InteractionAxis::Y, true) => "x",
+            (InteractionAxis::Y, false) | (InteractionAxis::X, true) => "y"
+        };
+        serializer.serialize_str(axis)
+    }
 }
 
+/// Where the tooltip is placed
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TooltipPosition{
+    ///average position of the items
+    Average,
+    ///position of the item closest to the pointer
+    Nearest
+}
 
 impl Default for Tooltip {
     fn default() -> Self {
@@ -926,34 +2594,242 @@ impl Default for Tooltip {
             mode: None,
             background_color: None,
             title_color: None,
-            callbacks: Some(TooltipCallbacks::default())
+            callbacks: Some(TooltipCallbacks::default()),
+            intersect: None,
+            position: None,
+            axis: None,
+            body_color: None,
+            footer_color: None,
+            title_font: None,
+            body_font: None,
+            footer_font: None,
+            padding: None,
+            caret_size: None,
+            corner_radius: None,
+            display_colors: None,
+            box_width: None,
+            box_height: None,
+            item_sort: None,
+            filter: None,
+            external: None,
+            html: None
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct Tooltip{
     pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mode: Option<TooltipMode>,
-    pub background_color: Option<Rgb>,
-    pub title_color: Option<Rgb>,
+    ///only show the tooltip when the pointer is over an item, Chart.js defaults to true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intersect: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<TooltipPosition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub axis: Option<InteractionAxis>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<Paint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title_font: Option<Font>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_font: Option<Font>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer_font: Option<Font>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub padding: Option<Padding>,
+    ///size of the arrow pointing at the item in pixels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caret_size: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub corner_radius: Option<f32>,
+    ///color boxes next to the item labels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_colors: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub box_width: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub box_height: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_sort: Option<JsExpr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<JsExpr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external: Option<JsExpr>,
+    ///set by `with_external`
+    #[serde(skip)]
+    pub html: Option<ExternalTooltip>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub callbacks: Option<TooltipCallbacks>
 }
 
-#[derive(Debug, Clone)]
+impl Tooltip {
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: TooltipMode) -> Self {
+        self.mode = Some(mode);
+        self.map_deprecated_mode();
+        self
+    }
+
+    ///moves the deprecated `TooltipMode::Average` over to the position it stands for
+    #[allow(deprecated)]
+    pub(crate) fn map_deprecated_mode(&mut self) {
+        if self.mode == Some(TooltipMode::Average) {
+            self.mode = None;
+            self.position.get_or_insert(TooltipPosition::Average);
+        }
+    }
+
+    pub fn with_intersect(mut self, intersect: bool) -> Self {
+        self.intersect = Some(intersect);
+        self
+    }
+
+    pub fn with_position(mut self, position: TooltipPosition) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    pub fn with_axis(mut self, axis: InteractionAxis) -> Self {
+        self.axis = Some(axis);
+        self
+    }
+
+    pub fn with_background_color<C: Into<Paint>>(mut self, color: C) -> Self {
+        self.background_color = Some(color.into());
+        self
+    }
+
+    pub fn with_title_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.title_color = Some(color.into());
+        self
+    }
+
+    pub fn with_body_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.body_color = Some(color.into());
+        self
+    }
+
+    pub fn with_footer_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.footer_color = Some(color.into());
+        self
+    }
+
+    pub fn with_title_font(mut self, font: Font) -> Self {
+        self.title_font = Some(font);
+        self
+    }
+
+    pub fn with_body_font(mut self, font: Font) -> Self {
+        self.body_font = Some(font);
+        self
+    }
+
+    pub fn with_footer_font(mut self, font: Font) -> Self {
+        self.footer_font = Some(font);
+        self
+    }
+
+    pub fn with_padding(mut self, padding: Padding) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    pub fn with_caret_size(mut self, size: f32) -> Self {
+        self.caret_size = Some(size);
+        self
+    }
+
+    pub fn with_corner_radius(mut self, radius: f32) -> Self {
+        self.corner_radius = Some(radius);
+        self
+    }
+
+    pub fn with_display_colors(mut self, display: bool) -> Self {
+        self.display_colors = Some(display);
+        self
+    }
+
+    ///size of the color boxes, see `with_display_colors`
+    pub fn with_box_size(mut self, width: f32, height: f32) -> Self {
+        self.box_width = Some(width);
+        self.box_height = Some(height);
+        self
+    }
+
+    ///custom `function(a, b, data)` comparing two tooltip items, like `Array.prototype.sort`
+    pub fn with_item_sort(mut self, compare: JsExpr) -> Self {
+        self.item_sort = Some(compare);
+        self
+    }
+
+    ///custom `function(item, index, items, data)`, the item is left out when it returns false
+    pub fn with_filter(mut self, filter: JsExpr) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn with_callbacks(mut self, callbacks: TooltipCallbacks) -> Self {
+        self.callbacks = Some(callbacks);
+        self
+    }
+
+    ///HTML tooltip next to the chart instead of the one drawn on the canvas
+    pub fn with_external(mut self, html: ExternalTooltip) -> Self {
+        self.enabled = false;
+        self.external = Some(ExternalTooltip::external_fn());
+        self.html = Some(html);
+        self
+    }
+
+    ///title, body and footer from point metadata, replaces the callbacks the layout has lines for
+    pub fn with_layout(mut self, layout: &TooltipLayout) -> Self {
+        layout.apply(self.callbacks.get_or_insert_with(TooltipCallbacks::default));
+        self
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct TooltipCallbacks{
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub before_title: Option<JsExpr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<JsExpr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub after_title: Option<JsExpr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub before_body: Option<JsExpr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub before_label: Option<JsExpr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<JsExpr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub label_color: Option<JsExpr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub label_text_color: Option<JsExpr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub after_label: Option<JsExpr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub after_body: Option<JsExpr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub before_footer: Option<JsExpr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub footer: Option<JsExpr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub after_footer: Option<JsExpr>,
 }
 
@@ -961,7 +2837,7 @@ impl Default for TooltipCallbacks{
     fn default() -> Self {
         TooltipCallbacks{
             before_title: None,
-            title: Some(JsExpr(DISPLAY_FN)),
+            title: Some(JsExpr::new(DISPLAY_FN)),
             after_title: None,
             before_body: None,
             before_label: None,
@@ -978,8 +2854,33 @@ impl Default for TooltipCallbacks{
 }
 
 
-#[derive(Debug, Clone)]
-pub struct JsExpr(pub &'static str);
+/// Raw JS code, put into the chart as it is.
+///
+/// Use [`JsFunction`] to build a function around runtime values, never format them into the code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsExpr(Cow<'static, str>);
+
+impl JsExpr {
+    pub fn new<S: Into<Cow<'static, str>>>(code: S) -> Self {
+        JsExpr(code.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&'static str> for JsExpr {
+    fn from(value: &'static str) -> Self {
+        JsExpr::new(value)
+    }
+}
+
+impl Serialize for JsExpr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        serialize_raw(&self.0, serializer)
+    }
+}
 
 impl std::fmt::Display for JsExpr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -988,3 +2889,128 @@ impl std::fmt::Display for JsExpr {
     }
 }
 
+/// Builds a JS function whose body reads Rust values as JSON-encoded constants
+///
+/// `JsFunction::new(&["value"])?.bind("unit", &unit)?.body("return value + ' ' + unit;")`
+/// gives `function(value){ const unit = "kWh"; return value + ' ' + unit; }`.
+#[derive(Debug, Clone)]
+pub struct JsFunction {
+    params: Vec<&'static str>,
+    bindings: Vec<(&'static str, String)>
+}
+
+/// Parameter or constant of a [`JsFunction`] that can't be used
+#[derive(Debug)]
+pub enum JsFunctionError {
+    ///not a plain JS identifier
+    InvalidName(&'static str),
+    ///reserved word, or a name strict mode doesn't allow to declare
+    ReservedName(&'static str),
+    ///already used by a parameter or another constant
+    DuplicateName(&'static str),
+    Value(serde_json::Error)
+}
+
+impl From<serde_json::Error> for JsFunctionError {
+    fn from(value: serde_json::Error) -> Self {
+        JsFunctionError::Value(value)
+    }
+}
+
+impl std::fmt::Display for JsFunctionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsFunctionError::InvalidName(name) => write!(f, "{:?} is not a JS identifier", name),
+            JsFunctionError::ReservedName(name) => write!(f, "{:?} is a reserved JS name", name),
+            JsFunctionError::DuplicateName(name) => write!(f, "{:?} is declared twice", name),
+            JsFunctionError::Value(err) => write!(f, "value can't be serialized: {}", err)
+        }
+    }
+}
+
+impl std::error::Error for JsFunctionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsFunctionError::Value(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+const JS_RESERVED: [&str; 46] = [
+    "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete", "do", "else",
+    "enum", "export", "extends", "false", "finally", "for", "function", "if", "import", "in", "instanceof", "new",
+    "null", "return", "super", "switch", "this", "throw", "true", "try", "typeof", "var", "void", "while", "with",
+    "yield", "let", "static", "implements", "interface", "package", "private", "protected", "public"
+];
+
+fn check_js_name(name: &'static str, declared: &[&'static str]) -> Result<(), JsFunctionError> {
+    let mut chars = name.chars();
+    let identifier = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if !identifier {
+        Err(JsFunctionError::InvalidName(name))
+    } else if JS_RESERVED.contains(&name) || name == "arguments" || name == "eval" {
+        Err(JsFunctionError::ReservedName(name))
+    } else if declared.contains(&name) {
+        Err(JsFunctionError::DuplicateName(name))
+    } else {
+        Ok(())
+    }
+}
+
+impl JsFunction {
+
+    pub fn new(params: &[&'static str]) -> Result<Self, JsFunctionError> {
+        for (i, param) in params.iter().enumerate() {
+            check_js_name(param, &params[..i])?;
+        }
+        Ok(Self { params: params.to_vec(), bindings: vec![] })
+    }
+
+    ///`value` as a constant named `name` in the body
+    pub fn bind<T: Serialize + ?Sized>(mut self, name: &'static str, value: &T) -> Result<Self, JsFunctionError> {
+        let declared: Vec<&'static str> = self.params.iter().copied().chain(self.bindings.iter().map(|(n, _)| *n)).collect();
+        check_js_name(name, &declared)?;
+        self.bindings.push((name, try_js_literal(value)?));
+        Ok(self)
+    }
+
+    pub fn body(self, body: &str) -> JsExpr {
+        let mut code = format!("function({}){{ ", self.params.join(", "));
+        for (name, value) in &self.bindings {
+            code.push_str(&format!("const {} = {}; ", name, value));
+        }
+        code.push_str(body);
+        code.push_str(" }");
+        JsExpr::new(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dataset_style_test() {
+        let dataset = Dataset::new(ChartType::Bar, "set".to_string(), [(1.0, 2.0)])
+            .with_style(DatasetStyle::default()
+                .with_border_color(Rgb(0, 0, 255))
+                .with_bar_thickness(BarThickness::Flex)
+                .with_border_skipped(BorderSkipped::None)
+                .with_clip(Clip::Disabled));
+        assert_eq!(
+            serde_json::to_string(&dataset).unwrap(),
+            r#"{"type":"bar","label":"set","data":[{"x":1.0,"y":2.0}],"borderColor":"rgb(0, 0, 255)","clip":false,"barThickness":"flex","borderSkipped":false}"#
+        );
+        assert_eq!(serde_json::to_string(&PointStyle::RectRounded).unwrap(), r#""rectRounded""#);
+        assert_eq!(serde_json::to_string(&PointStyle::CrossRot).unwrap(), r#""crossRot""#);
+    }
+
+    #[test]
+    fn from_f64_test() {
+        assert_eq!(<f32 as AsF64>::from_f64(2.5), Some(2.5));
+        assert_eq!(<f32 as AsF64>::from_f64(1e300), None);
+        assert_eq!(<f32 as AsF64>::from_f64(f64::NEG_INFINITY), Some(f32::NEG_INFINITY));
+    }
+}
@@ -0,0 +1,395 @@
+use crate::common::{Color, Rgb};
+
+/// Colors datasets get when they don't have their own, see `ChartConfig::with_auto_colors`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Palette {
+    Tableau10,
+    ColorBrewer(BrewerSet),
+    ///colorblind-safe palette by Okabe and Ito
+    OkabeIto,
+    Custom(Vec<Rgb>)
+}
+
+/// Qualitative ColorBrewer sets
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BrewerSet {
+    Set1,
+    Set2,
+    Set3,
+    Dark2,
+    Paired,
+    Pastel1,
+    Accent
+}
+
+const TABLEAU10: [Rgb; 10] = [
+    Rgb(0x4e, 0x79, 0xa7), Rgb(0xf2, 0x8e, 0x2b), Rgb(0xe1, 0x57, 0x59), Rgb(0x76, 0xb7, 0xb2), Rgb(0x59, 0xa1, 0x4f),
+    Rgb(0xed, 0xc9, 0x48), Rgb(0xb0, 0x7a, 0xa1), Rgb(0xff, 0x9d, 0xa7), Rgb(0x9c, 0x75, 0x5f), Rgb(0xba, 0xb0, 0xac)
+];
+
+const OKABE_ITO: [Rgb; 8] = [
+    Rgb(0xe6, 0x9f, 0x00), Rgb(0x56, 0xb4, 0xe9), Rgb(0x00, 0x9e, 0x73), Rgb(0xf0, 0xe4, 0x42),
+    Rgb(0x00, 0x72, 0xb2), Rgb(0xd5, 0x5e, 0x00), Rgb(0xcc, 0x79, 0xa7), Rgb(0x00, 0x00, 0x00)
+];
+
+const SET1: [Rgb; 9] = [
+    Rgb(0xe4, 0x1a, 0x1c), Rgb(0x37, 0x7e, 0xb8), Rgb(0x4d, 0xaf, 0x4a), Rgb(0x98, 0x4e, 0xa3), Rgb(0xff, 0x7f, 0x00),
+    Rgb(0xff, 0xff, 0x33), Rgb(0xa6, 0x56, 0x28), Rgb(0xf7, 0x81, 0xbf), Rgb(0x99, 0x99, 0x99)
+];
+
+const SET2: [Rgb; 8] = [
+    Rgb(0x66, 0xc2, 0xa5), Rgb(0xfc, 0x8d, 0x62), Rgb(0x8d, 0xa0, 0xcb), Rgb(0xe7, 0x8a, 0xc3),
+    Rgb(0xa6, 0xd8, 0x54), Rgb(0xff, 0xd9, 0x2f), Rgb(0xe5, 0xc4, 0x94), Rgb(0xb3, 0xb3, 0xb3)
+];
+
+const SET3: [Rgb; 12] = [
+    Rgb(0x8d, 0xd3, 0xc7), Rgb(0xff, 0xff, 0xb3), Rgb(0xbe, 0xba, 0xda), Rgb(0xfb, 0x80, 0x72), Rgb(0x80, 0xb1, 0xd3), Rgb(0xfd, 0xb4, 0x62),
+    Rgb(0xb3, 0xde, 0x69), Rgb(0xfc, 0xcd, 0xe5), Rgb(0xd9, 0xd9, 0xd9), Rgb(0xbc, 0x80, 0xbd), Rgb(0xcc, 0xeb, 0xc5), Rgb(0xff, 0xed, 0x6f)
+];
+
+const DARK2: [Rgb; 8] = [
+    Rgb(0x1b, 0x9e, 0x77), Rgb(0xd9, 0x5f, 0x02), Rgb(0x75, 0x70, 0xb3), Rgb(0xe7, 0x29, 0x8a),
+    Rgb(0x66, 0xa6, 0x1e), Rgb(0xe6, 0xab, 0x02), Rgb(0xa6, 0x76, 0x1d), Rgb(0x66, 0x66, 0x66)
+];
+
+const PAIRED: [Rgb; 12] = [
+    Rgb(0xa6, 0xce, 0xe3), Rgb(0x1f, 0x78, 0xb4), Rgb(0xb2, 0xdf, 0x8a), Rgb(0x33, 0xa0, 0x2c), Rgb(0xfb, 0x9a, 0x99), Rgb(0xe3, 0x1a, 0x1c),
+    Rgb(0xfd, 0xbf, 0x6f), Rgb(0xff, 0x7f, 0x00), Rgb(0xca, 0xb2, 0xd6), Rgb(0x6a, 0x3d, 0x9a), Rgb(0xff, 0xff, 0x99), Rgb(0xb1, 0x59, 0x28)
+];
+
+const PASTEL1: [Rgb; 9] = [
+    Rgb(0xfb, 0xb4, 0xae), Rgb(0xb3, 0xcd, 0xe3), Rgb(0xcc, 0xeb, 0xc5), Rgb(0xde, 0xcb, 0xe4), Rgb(0xfe, 0xd9, 0xa6),
+    Rgb(0xff, 0xff, 0xcc), Rgb(0xe5, 0xd8, 0xbd), Rgb(0xfd, 0xda, 0xec), Rgb(0xf2, 0xf2, 0xf2)
+];
+
+const ACCENT: [Rgb; 8] = [
+    Rgb(0x7f, 0xc9, 0x7f), Rgb(0xbe, 0xae, 0xd4), Rgb(0xfd, 0xc0, 0x86), Rgb(0xff, 0xff, 0x99),
+    Rgb(0x38, 0x6c, 0xb0), Rgb(0xf0, 0x02, 0x7f), Rgb(0xbf, 0x5b, 0x17), Rgb(0x66, 0x66, 0x66)
+];
+
+impl Palette {
+
+    pub fn colors(&self) -> &[Rgb] {
+        match self {
+            Palette::Tableau10 => &TABLEAU10,
+            Palette::OkabeIto => &OKABE_ITO,
+            Palette::ColorBrewer(set) => match set {
+                BrewerSet::Set1 => &SET1,
+                BrewerSet::Set2 => &SET2,
+                BrewerSet::Set3 => &SET3,
+                BrewerSet::Dark2 => &DARK2,
+                BrewerSet::Paired => &PAIRED,
+                BrewerSet::Pastel1 => &PASTEL1,
+                BrewerSet::Accent => &ACCENT
+            },
+            Palette::Custom(colors) => colors
+        }
+    }
+}
+
+/// How datasets are matched to palette colors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorAssignment {
+    ///color is picked by a hash of the label, so a label has the same color on every chart.
+    ///When an earlier label of the chart has that color already, the next free one is used instead,
+    ///labels only share a color once the palette runs out.
+    ByLabel,
+    ///colors go in palette order, the way datasets were added
+    ByOrder
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoColors {
+    palette: Palette,
+    background_alpha: f32,
+    assignment: ColorAssignment
+}
+
+impl AutoColors {
+
+    pub fn new(palette: Palette) -> Self {
+        Self {
+            palette,
+            background_alpha: 0.5,
+            assignment: ColorAssignment::ByLabel
+        }
+    }
+
+    ///opacity of the background color derived from the border one, 0.5 by default
+    pub fn with_background_alpha(mut self, alpha: f32) -> Self {
+        self.background_alpha = alpha;
+        self
+    }
+
+    pub fn with_assignment(mut self, assignment: ColorAssignment) -> Self {
+        self.assignment = assignment;
+        self
+    }
+
+    pub fn background_alpha(&self) -> f32 {
+        self.background_alpha
+    }
+
+    /// Border colors of the datasets of a chart with the given labels, in order, empty for an empty palette.
+    ///
+    /// Unlike `color_for`, labels of the chart get different colors as long as the palette has enough of them,
+    /// datasets with the same label get the same color.
+    pub fn colors_for(&self, labels: &[&str]) -> Vec<&Rgb> {
+        let colors = self.palette.colors();
+        if colors.is_empty() {
+            return vec![];
+        }
+        let mut taken: Vec<(&str, usize)> = Vec::with_capacity(labels.len());
+        labels.iter().enumerate().map(|(index, label)| {
+            let slot = match (self.assignment, taken.iter().find(|(l, _)| l == label)) {
+                (ColorAssignment::ByOrder, _) => index % colors.len(),
+                (ColorAssignment::ByLabel, Some((_, slot))) => *slot,
+                (ColorAssignment::ByLabel, None) => {
+                    let start = (label_hash(label) % colors.len() as u64) as usize;
+                    let slot = (0..colors.len())
+                        .map(|step| (start + step) % colors.len())
+                        .find(|slot| !taken.iter().any(|(_, s)| s == slot))
+                        .unwrap_or(start);
+                    taken.push((label, slot));
+                    slot
+                }
+            };
+            &colors[slot]
+        }).collect()
+    }
+
+    /// Border color of the `index`-th dataset with the given label on its own, `None` for an empty palette
+    pub fn color_for(&self, index: usize, label: &str) -> Option<&Rgb> {
+        let colors = self.palette.colors();
+        if colors.is_empty() {
+            return None;
+        }
+        let slot = match self.assignment {
+            ColorAssignment::ByLabel => (label_hash(label) % colors.len() as u64) as usize,
+            ColorAssignment::ByOrder => index % colors.len()
+        };
+        colors.get(slot)
+    }
+}
+
+/// FNV-1a, unlike the std hasher it is guaranteed to stay the same between releases
+fn label_hash(label: &str) -> u64 {
+    label.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+
+/// Maps a value to a color, to color points by a third value, see `Dataset::with_value_colors`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorScale {
+    ///colors evenly spread over the domain and blended in between.
+    ///Domain is the range of the values when not set.
+    Continuous { colors: Vec<Color>, domain: Option<(f64, f64)> },
+    ///`below` under the first threshold, past it the color of the highest threshold reached
+    Thresholds { below: Color, steps: Vec<(f64, Color)> }
+}
+
+const VIRIDIS: [Rgb; 10] = [
+    Rgb(0x44, 0x01, 0x54), Rgb(0x48, 0x28, 0x78), Rgb(0x3e, 0x49, 0x89), Rgb(0x31, 0x68, 0x8e), Rgb(0x26, 0x82, 0x8e),
+    Rgb(0x1f, 0x9e, 0x89), Rgb(0x35, 0xb7, 0x79), Rgb(0x6e, 0xce, 0x58), Rgb(0xb5, 0xde, 0x2b), Rgb(0xfd, 0xe7, 0x25)
+];
+
+/// Color of values that can't be placed on a scale, like NaN
+const NO_VALUE: Color = Color::Rgba(0x99, 0x99, 0x99, 1.0);
+
+impl ColorScale {
+
+    pub fn continuous(colors: Vec<Color>) -> Self {
+        ColorScale::Continuous { colors, domain: None }
+    }
+
+    ///perceptually uniform, readable with color vision deficiencies
+    pub fn viridis() -> Self {
+        Self::continuous(VIRIDIS.iter().map(|c| c.clone().into()).collect())
+    }
+
+    pub fn diverging(low: Color, mid: Color, high: Color) -> Self {
+        Self::continuous(vec![low, mid, high])
+    }
+
+    ///red for low values, green for high ones, through yellow
+    pub fn red_green() -> Self {
+        Self::diverging(Color::rgb(0xd7, 0x30, 0x27), Color::rgb(0xff, 0xff, 0xbf), Color::rgb(0x1a, 0x98, 0x50))
+    }
+
+    pub fn thresholds(below: Color, steps: Vec<(f64, Color)>) -> Self {
+        let mut steps = steps;
+        steps.sort_by(|a, b| a.0.total_cmp(&b.0));
+        ColorScale::Thresholds { below, steps }
+    }
+
+    ///fixed value range for continuous scales, values outside of it get the end colors.
+    ///`min` above `max` reverses the scale.
+    pub fn with_domain(self, min: f64, max: f64) -> Self {
+        match self {
+            ColorScale::Continuous { colors, .. } => ColorScale::Continuous { colors, domain: Some((min, max)) },
+            thresholds => thresholds
+        }
+    }
+
+    /// Value range colors are spread over, `None` when there are no finite values to take it from
+    pub fn domain(&self, values: &[f64]) -> Option<(f64, f64)> {
+        if let ColorScale::Continuous { domain: Some(domain), .. } = self {
+            return Some(*domain);
+        }
+        let finite = values.iter().copied().filter(|v| v.is_finite());
+        let min = finite.clone().reduce(f64::min)?;
+        let max = finite.reduce(f64::max)?;
+        Some((min, max))
+    }
+
+    fn color_in(&self, value: f64, domain: Option<(f64, f64)>) -> Color {
+        if !value.is_finite() {
+            return NO_VALUE;
+        }
+        match self {
+            ColorScale::Continuous { colors, .. } => {
+                let (Some((min, max)), Some(last)) = (domain, colors.len().checked_sub(1)) else {
+                    return colors.first().cloned().unwrap_or(NO_VALUE);
+                };
+                let t = if max != min { ((value - min) / (max - min)).clamp(0.0, 1.0) } else { 0.5 };
+                let position = t * last as f64;
+                let i = (position.floor() as usize).min(last.saturating_sub(1));
+                match colors.get(i + 1) {
+                    Some(next) => colors[i].clone().mix(next, (position - i as f64) as f32),
+                    None => colors[i].clone()
+                }
+            }
+            ColorScale::Thresholds { below, steps } => steps.iter()
+                .rev()
+                .find(|(threshold, _)| value >= *threshold)
+                .map_or_else(|| below.clone(), |(_, color)| color.clone())
+        }
+    }
+
+    pub fn color_for(&self, value: f64, values: &[f64]) -> Color {
+        self.color_in(value, self.domain(values))
+    }
+
+    /// Color of each value, continuous scales spread over the range of `values` unless they have a domain
+    pub fn colors_for(&self, values: &[f64]) -> Vec<Color> {
+        let domain = self.domain(values);
+        values.iter().map(|v| self.color_in(*v, domain)).collect()
+    }
+
+    /// CSS `linear-gradient` from the low to the high end of the domain
+    fn css_gradient(&self, (min, max): (f64, f64)) -> String {
+        let stops: Vec<String> = match self {
+            ColorScale::Continuous { colors, .. } => {
+                let last = colors.len().saturating_sub(1).max(1) as f64;
+                colors.iter().enumerate()
+                    .map(|(i, c)| format!("{} {:.2}%", c, i as f64 / last * 100.0))
+                    .collect()
+            }
+            ColorScale::Thresholds { below, steps } => {
+                let mut stops = vec![format!("{} 0%", below)];
+                let mut previous = below;
+                for (threshold, color) in steps {
+                    let at = if max > min { ((threshold - min) / (max - min)).clamp(0.0, 1.0) * 100.0 } else { 0.0 };
+                    stops.push(format!("{} {:.2}%", previous, at));
+                    stops.push(format!("{} {:.2}%", color, at));
+                    previous = color;
+                }
+                stops.push(format!("{} 100%", previous));
+                stops
+            }
+        };
+        format!("linear-gradient(to right, {})", stops.join(", "))
+    }
+}
+
+/// Color bar shown under a chart, with the low and high values at its ends
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorLegend {
+    pub(crate) title: String,
+    pub(crate) min: String,
+    pub(crate) max: String,
+    pub(crate) gradient: String
+}
+
+impl ColorLegend {
+
+    /// Legend for `scale` applied to `values`, ends are labeled with the domain
+    pub fn new(title: &str, scale: &ColorScale, values: &[f64]) -> Self {
+        let (min, max) = scale.domain(values).unwrap_or((0.0, 0.0));
+        Self {
+            title: title.to_string(),
+            min: min.to_string(),
+            max: max.to_string(),
+            gradient: scale.css_gradient((min, max))
+        }
+    }
+
+    ///text at the low and high ends, like "0%" and "5%"
+    pub fn with_labels(mut self, min: &str, max: &str) -> Self {
+        self.min = min.to_string();
+        self.max = max.to_string();
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sailfish::TemplateSimple;
+    use crate::{ChartConfig, ChartType, Dataset, NonFinitePolicy, ValueColorsError};
+    use crate::common::Size;
+
+    #[test]
+    fn stable_label_color_test() {
+        let colors = AutoColors::new(Palette::Tableau10);
+        let prod = colors.color_for(0, "prod");
+        assert_eq!(prod, colors.color_for(5, "prod"));
+        assert_eq!(prod, Some(&TABLEAU10[(label_hash("prod") % 10) as usize]));
+        let ordered = colors.with_assignment(ColorAssignment::ByOrder);
+        assert_eq!(ordered.color_for(11, "prod"), Some(&TABLEAU10[1]));
+        assert_eq!(AutoColors::new(Palette::Custom(vec![])).color_for(0, "prod"), None);
+
+        //labels hashing to the same slot get different colors within a chart
+        let two = AutoColors::new(Palette::Custom(vec![Rgb(1, 1, 1), Rgb(2, 2, 2)]));
+        let (a, b) = ("a", (0..).map(|i| format!("b{}", i)).find(|b| label_hash(b) % 2 == label_hash("a") % 2).unwrap());
+        assert_eq!(two.color_for(0, "a"), two.color_for(1, &b));
+        let colors = two.colors_for(&[a, &b, a, "c"]);
+        assert_ne!(colors[0], colors[1]);
+        assert_eq!(colors[0], colors[2]);
+        assert_eq!(colors[0], two.color_for(0, a).unwrap());
+        assert_eq!(colors[3], two.color_for(3, "c").unwrap());
+    }
+
+    #[test]
+    fn color_scale_test() {
+        let scale = ColorScale::diverging(Color::rgb(255, 0, 0), Color::rgb(255, 255, 0), Color::rgb(0, 255, 0));
+        let colors = scale.colors_for(&[0.0, 5.0, 7.5, 10.0, f64::NAN]);
+        assert_eq!(colors[0..4], [Color::rgb(255, 0, 0), Color::rgb(255, 255, 0), Color::rgb(128, 255, 0), Color::rgb(0, 255, 0)]);
+        assert_eq!(colors[4], NO_VALUE);
+        let thresholds = ColorScale::thresholds(Color::named("green").unwrap(), vec![(5.0, Color::named("red").unwrap()), (1.0, Color::named("orange").unwrap())]);
+        assert_eq!(thresholds.color_for(0.5, &[]), Color::named("green").unwrap());
+        assert_eq!(thresholds.color_for(2.0, &[]), Color::named("orange").unwrap());
+        assert_eq!(
+            ColorLegend::new("errors", &thresholds, &[0.0, 10.0]).gradient,
+            "linear-gradient(to right, green 0%, green 10.00%, orange 10.00%, orange 50.00%, red 50.00%, red 100%)"
+        );
+    }
+
+    #[test]
+    fn value_colors_test() {
+        let scale = ColorScale::thresholds(Color::named("green").unwrap(), vec![(1.0, Color::named("red").unwrap())]);
+        let dataset = || Dataset::new(ChartType::Scatter, "set".to_string(), [(1.0, 2.0), (2.0, f64::NAN), (3.0, 4.0)]);
+        assert_eq!(dataset().with_value_colors(&[0.0, 2.0], &scale).unwrap_err(), ValueColorsError{ points: 3, values: 2 });
+        //the color of a skipped point goes with it
+        let html = ChartConfig::<f64, f64>::default()
+            .with_non_finite_policy(NonFinitePolicy::Skip)
+            .add_series_direct(dataset().with_value_colors(&[0.0, 2.0, 0.0], &scale).unwrap())
+            .build(Size::pixels(600), Size::pixels(400))
+            .render_once()
+            .unwrap();
+        assert!(html.contains(
+            r#""data":[{"x":1.0,"y":2.0},{"x":3.0,"y":4.0}],"pointBorderColor":["green","green"],"pointBackgroundColor":["green","green"]"#
+        ));
+    }
+}
@@ -0,0 +1,430 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+use sailfish::RenderError;
+use sailfish::runtime::{Buffer, Render};
+use serde::Serialize;
+use crate::js::{js_literal, to_js};
+
+/// Where a script is loaded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptSource {
+    ///npm package served by jsDelivr, latest version and the package's main file when not set
+    Cdn { package: Cow<'static, str>, version: Option<Cow<'static, str>>, file: Option<Cow<'static, str>> },
+    Url(Cow<'static, str>),
+    ///script content, put inline into the page
+    Embedded(Cow<'static, str>)
+}
+
+const JSDELIVR_NPM: &str = "https://cdn.jsdelivr.net/npm/";
+
+impl ScriptSource {
+
+    pub fn cdn<S: Into<Cow<'static, str>>>(package: S) -> Self {
+        ScriptSource::Cdn { package: package.into(), version: None, file: None }
+    }
+
+    pub fn cdn_version<S: Into<Cow<'static, str>>, V: Into<Cow<'static, str>>>(package: S, version: V) -> Self {
+        ScriptSource::Cdn { package: package.into(), version: Some(version.into()), file: None }
+    }
+
+    ///file of a CDN package instead of its main one, like `dist/plugin.min.js`
+    pub fn with_file<S: Into<Cow<'static, str>>>(self, path: S) -> Self {
+        match self {
+            ScriptSource::Cdn { package, version, .. } => ScriptSource::Cdn { package, version, file: Some(path.into()) },
+            other => other
+        }
+    }
+
+    pub fn url<S: Into<Cow<'static, str>>>(url: S) -> Self {
+        ScriptSource::Url(url.into())
+    }
+
+    pub fn embedded<S: Into<Cow<'static, str>>>(code: S) -> Self {
+        ScriptSource::Embedded(code.into())
+    }
+
+    /// Address the script is loaded from, `None` for embedded scripts
+    pub fn src(&self) -> Option<Cow<'_, str>> {
+        match self {
+            ScriptSource::Cdn { package, version, file } => {
+                let mut url = format!("{}{}", JSDELIVR_NPM, package);
+                if let Some(version) = version {
+                    url.push('@');
+                    url.push_str(version);
+                }
+                if let Some(file) = file {
+                    url.push('/');
+                    url.push_str(file.trim_start_matches('/'));
+                }
+                Some(Cow::Owned(url))
+            }
+            ScriptSource::Url(url) => Some(Cow::Borrowed(url)),
+            ScriptSource::Embedded(_) => None
+        }
+    }
+
+    /// Package, version and file of scripts from npm on jsDelivr, also when given as a URL
+    fn npm_parts(&self) -> Option<(&str, Option<&str>, &str)> {
+        match self {
+            ScriptSource::Cdn { package, version, file } =>
+                Some((package, version.as_deref(), file.as_deref().map_or("", |f| f.trim_start_matches('/')))),
+            ScriptSource::Url(url) => {
+                let path = url.strip_prefix(JSDELIVR_NPM)?;
+                //scoped packages have one more path segment, `@scope/name@version/file`
+                let name_end = if path.starts_with('@') {
+                    let slash = path.find('/')?;
+                    slash + 1 + path[slash + 1..].find('/').unwrap_or(path.len() - slash - 1)
+                } else {
+                    path.find('/').unwrap_or(path.len())
+                };
+                let (spec, file) = path.split_at(name_end);
+                let (package, version) = match spec.char_indices().skip(1).find(|(_, c)| *c == '@') {
+                    Some((at, _)) => (&spec[..at], Some(&spec[at + 1..])),
+                    None => (spec, None)
+                };
+                if package.is_empty() {
+                    return None;
+                }
+                Some((package, version, file.trim_start_matches('/')))
+            }
+            ScriptSource::Embedded(_) => None
+        }
+    }
+
+    /// Same script, even if it is given in a different way or in another version of the same npm package
+    fn same_as(&self, other: &ScriptSource) -> bool {
+        match (self, other) {
+            (ScriptSource::Embedded(a), ScriptSource::Embedded(b)) => a == b,
+            (a, b) => match (a.npm_parts(), b.npm_parts()) {
+                (Some((a_package, _, a_file)), Some((b_package, _, b_file))) => a_package == b_package && a_file == b_file,
+                _ => a.src().is_some() && a.src() == b.src()
+            }
+        }
+    }
+
+    fn has_version(&self) -> bool {
+        self.npm_parts().is_some_and(|(_, version, _)| version.is_some())
+    }
+}
+
+impl From<&'static str> for ScriptSource {
+    fn from(value: &'static str) -> Self {
+        ScriptSource::url(value)
+    }
+}
+
+/// A Chart.js plugin a chart can use.
+///
+/// Built-in plugins (annotations, zoom, data labels) are added by the chart itself,
+/// others are attached with `ChartConfig::with_plugin` or `ChartConfig::with_plugin_options`.
+pub trait ChartPlugin {
+    ///key of the plugin options under `options.plugins`, the plugin id
+    fn options_key(&self) -> Cow<'static, str>;
+
+    ///scripts the plugin needs, in load order, after Chart.js
+    fn scripts(&self) -> Vec<ScriptSource> {
+        vec![]
+    }
+
+    ///JS run once per page after the scripts are loaded, like `Chart.register(MyPlugin);`
+    fn registration(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    ///JS expression of the plugin object, to enable it in the chart's `plugins` array instead of registering it for every chart
+    fn local_instance(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+}
+
+/// Chart.js plugin hook, every hook gets `(chart, args, options)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginHook {
+    BeforeInit,
+    AfterInit,
+    BeforeUpdate,
+    AfterUpdate,
+    BeforeLayout,
+    AfterLayout,
+    BeforeDatasetsDraw,
+    AfterDatasetsDraw,
+    BeforeDraw,
+    AfterDraw,
+    BeforeTooltipDraw,
+    AfterTooltipDraw,
+    ///`args.event` is the event, set `args.changed = true` to have the chart redrawn
+    BeforeEvent,
+    AfterEvent,
+    Resize,
+    BeforeDestroy,
+    AfterDestroy
+}
+
+impl PluginHook {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PluginHook::BeforeInit => "beforeInit",
+            PluginHook::AfterInit => "afterInit",
+            PluginHook::BeforeUpdate => "beforeUpdate",
+            PluginHook::AfterUpdate => "afterUpdate",
+            PluginHook::BeforeLayout => "beforeLayout",
+            PluginHook::AfterLayout => "afterLayout",
+            PluginHook::BeforeDatasetsDraw => "beforeDatasetsDraw",
+            PluginHook::AfterDatasetsDraw => "afterDatasetsDraw",
+            PluginHook::BeforeDraw => "beforeDraw",
+            PluginHook::AfterDraw => "afterDraw",
+            PluginHook::BeforeTooltipDraw => "beforeTooltipDraw",
+            PluginHook::AfterTooltipDraw => "afterTooltipDraw",
+            PluginHook::BeforeEvent => "beforeEvent",
+            PluginHook::AfterEvent => "afterEvent",
+            PluginHook::Resize => "resize",
+            PluginHook::BeforeDestroy => "beforeDestroy",
+            PluginHook::AfterDestroy => "afterDestroy"
+        }
+    }
+}
+
+/// One-off plugin written as JS hook bodies, like a watermark or a crosshair.
+///
+/// It goes into the `plugins` array of the charts it is attached to, not into the global registry.
+/// Its options come from `ChartConfig::with_plugin_options` and are read as `options` in the hooks.
+#[derive(Debug, Clone)]
+pub struct InlinePlugin {
+    id: Cow<'static, str>,
+    hooks: Vec<(PluginHook, Cow<'static, str>)>
+}
+
+impl InlinePlugin {
+
+    ///`id` is also the options key under `options.plugins`
+    pub fn new<S: Into<Cow<'static, str>>>(id: S) -> Self {
+        Self { id: id.into(), hooks: vec![] }
+    }
+
+    ///`body` of `function(chart, args, options)`, replaces an earlier body of the same hook
+    pub fn with_hook<S: Into<Cow<'static, str>>>(mut self, hook: PluginHook, body: S) -> Self {
+        let body = body.into();
+        match self.hooks.iter_mut().find(|(h, _)| *h == hook) {
+            Some((_, existing)) => *existing = body,
+            None => self.hooks.push((hook, body))
+        }
+        self
+    }
+}
+
+impl ChartPlugin for InlinePlugin {
+    fn options_key(&self) -> Cow<'static, str> {
+        self.id.clone()
+    }
+
+    fn local_instance(&self) -> Option<Cow<'static, str>> {
+        let mut js = format!("{{id: {}", js_literal(&self.id));
+        for (hook, body) in &self.hooks {
+            //write! to a String can't fail
+            let _ = write!(js, ", {}: function(chart, args, options){{ {} }}", hook.name(), body);
+        }
+        js.push('}');
+        Some(Cow::Owned(js))
+    }
+}
+
+/// Plugin attached to a chart config, with its options already turned into JS
+#[derive(Debug, Clone)]
+pub(crate) struct AttachedPlugin {
+    pub(crate) key: Cow<'static, str>,
+    pub(crate) scripts: Vec<ScriptSource>,
+    pub(crate) registration: Option<Cow<'static, str>>,
+    pub(crate) local_instance: Option<Cow<'static, str>>,
+    pub(crate) options: Option<String>
+}
+
+impl AttachedPlugin {
+
+    pub(crate) fn new<P: ChartPlugin + ?Sized>(plugin: &P) -> Self {
+        Self {
+            key: plugin.options_key(),
+            scripts: plugin.scripts(),
+            registration: plugin.registration(),
+            local_instance: plugin.local_instance(),
+            options: None
+        }
+    }
+
+    pub(crate) fn with_options<O: Serialize + ?Sized>(mut self, options: &O) -> Result<Self, serde_json::Error> {
+        self.options = Some(to_js(options)?);
+        Ok(self)
+    }
+}
+
+/// Scripts and registration code charts need, each kept once, in the order it was first needed.
+///
+/// A page with several charts merges the dependencies of all of them, see `OnePage::add_chart`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dependencies {
+    scripts: Vec<ScriptSource>,
+    registrations: Vec<Cow<'static, str>>
+}
+
+impl Dependencies {
+
+    /// Adds a script unless it is there already, a pinned version of a package replaces an unpinned one
+    pub fn add_script<S: Into<ScriptSource>>(&mut self, script: S) {
+        let script = script.into();
+        match self.scripts.iter_mut().find(|s| s.same_as(&script)) {
+            Some(existing) => if !existing.has_version() && script.has_version() {
+                *existing = script;
+            },
+            None => self.scripts.push(script)
+        }
+    }
+
+    pub fn add_registration<S: Into<Cow<'static, str>>>(&mut self, code: S) {
+        let code = code.into();
+        if !self.registrations.contains(&code) {
+            self.registrations.push(code);
+        }
+    }
+
+    pub fn add_plugin<P: ChartPlugin + ?Sized>(&mut self, plugin: &P) {
+        self.add_attached(&AttachedPlugin::new(plugin));
+    }
+
+    pub(crate) fn add_attached(&mut self, plugin: &AttachedPlugin) {
+        for script in &plugin.scripts {
+            self.add_script(script.clone());
+        }
+        if let Some(code) = &plugin.registration {
+            self.add_registration(code.clone());
+        }
+    }
+
+    pub fn merge(&mut self, other: &Dependencies) {
+        for script in &other.scripts {
+            self.add_script(script.clone());
+        }
+        for code in &other.registrations {
+            self.add_registration(code.clone());
+        }
+    }
+
+    pub fn scripts(&self) -> &[ScriptSource] {
+        &self.scripts
+    }
+
+    pub fn registrations(&self) -> &[Cow<'static, str>] {
+        &self.registrations
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty() && self.registrations.is_empty()
+    }
+}
+
+fn escape_attribute(value: &str) -> String {
+    value.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;")
+}
+
+/// Script tags, in order, then the registration code
+impl Render for Dependencies {
+    fn render(&self, b: &mut Buffer) -> Result<(), RenderError> {
+        for script in &self.scripts {
+            match script.src() {
+                Some(src) => writeln!(b, "<script src=\"{}\"></script>", escape_attribute(&src))?,
+                //"</" can't end the tag early once escaped, it means the same in JS strings and regular expressions
+                None => if let ScriptSource::Embedded(code) = script {
+                    writeln!(b, "<script>{}</script>", code.replace("</", "<\\/"))?
+                }
+            }
+        }
+        if !self.registrations.is_empty() {
+            writeln!(b, "<script>{}</script>", self.registrations.join("\n").replace("</", "<\\/"))?;
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sailfish::TemplateSimple;
+    use crate::{zoom, ChartConfig};
+    use crate::common::Size;
+    use crate::render::OnePage;
+
+    #[test]
+    fn dependencies_test() {
+        let mut deps = Dependencies::default();
+        deps.add_script(ScriptSource::cdn("hammerjs"));
+        deps.add_script(ScriptSource::cdn_version("chartjs-plugin-zoom", "2"));
+        deps.add_script("https://cdn.jsdelivr.net/npm/hammerjs@2.0.8");
+        deps.add_script("https://cdn.jsdelivr.net/npm/chartjs-plugin-zoom");
+        deps.add_script(ScriptSource::cdn_version("@scope/plugin".to_string(), "1").with_file("dist/plugin.min.js"));
+        deps.add_script("https://cdn.jsdelivr.net/npm/@scope/plugin@1.2/dist/plugin.min.js");
+        deps.add_script(ScriptSource::embedded("const a = '</script>';"));
+        deps.add_registration("Chart.register(ChartZoom);");
+        deps.add_registration("Chart.register(ChartZoom);");
+        let mut b = Buffer::new();
+        deps.render(&mut b).unwrap();
+        assert_eq!(
+            b.into_string(),
+            "<script src=\"https://cdn.jsdelivr.net/npm/hammerjs@2.0.8\"></script>\n\
+             <script src=\"https://cdn.jsdelivr.net/npm/chartjs-plugin-zoom@2\"></script>\n\
+             <script src=\"https://cdn.jsdelivr.net/npm/@scope/plugin@1/dist/plugin.min.js\"></script>\n\
+             <script>const a = '<\\/script>';</script>\n\
+             <script>Chart.register(ChartZoom);</script>\n"
+        );
+    }
+
+    #[test]
+    fn inline_plugin_test() {
+        let plugin = InlinePlugin::new("water\"mark")
+            .with_hook(PluginHook::BeforeDraw, "a();")
+            .with_hook(PluginHook::AfterEvent, "b();")
+            .with_hook(PluginHook::BeforeDraw, "c();");
+        assert_eq!(
+            plugin.local_instance().unwrap(),
+            r#"{id: "water\"mark", beforeDraw: function(chart, args, options){ c(); }, afterEvent: function(chart, args, options){ b(); }}"#
+        );
+    }
+
+    #[test]
+    fn plugin_dependencies_test() {
+        struct Crosshair;
+        impl ChartPlugin for Crosshair {
+            fn options_key(&self) -> Cow<'static, str> {
+                Cow::Borrowed("crosshair")
+            }
+            fn scripts(&self) -> Vec<ScriptSource> {
+                vec![zoom::HAMMER_SCRIPT.into(), ScriptSource::cdn("chartjs-plugin-crosshair")]
+            }
+        }
+        let chart = ChartConfig::<f64, f64>::default()
+            .with_zoom(zoom::ZoomConfig::default())
+            .with_plugin_options(&Crosshair, &serde_json::json!({"sync": {"enabled": false}})).unwrap()
+            .build(Size::pixels(600), Size::pixels(400));
+        let deps = chart.dependencies();
+        assert_eq!(deps.scripts(), [
+            zoom::HAMMER_SCRIPT.into(),
+            zoom::ZOOM_SCRIPT.into(),
+            ScriptSource::cdn("chartjs-plugin-crosshair")
+        ]);
+        assert_eq!(deps.registrations().len(), 1);
+        let registration = format!("<script>{}</script>", deps.registrations()[0]);
+        //a chart rendered on its own loads and registers its plugins, a page does it once in the head
+        let html = chart.render_once().unwrap();
+        assert!(html.contains(r#""crosshair": {"sync":{"enabled":false}},"#));
+        assert!(html.contains(&registration));
+        assert!(html.find(&registration).unwrap() < html.find("new Chart(").unwrap());
+        let zoom_chart = || ChartConfig::<f64, f64>::default()
+            .with_zoom(zoom::ZoomConfig::default())
+            .build(Size::pixels(600), Size::pixels(400));
+        let page = OnePage::new("page", "")
+            .add_chart(zoom_chart()).unwrap()
+            .add_chart(zoom_chart()).unwrap()
+            .render_once()
+            .unwrap();
+        assert_eq!(page.matches(&registration).count(), 1);
+        assert!(page.find(&registration).unwrap() < page.find("</head>").unwrap());
+    }
+}
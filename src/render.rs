@@ -1,22 +1,53 @@
-use sailfish::TemplateSimple;
+use std::borrow::Cow;
+use sailfish::{RenderError, TemplateSimple};
 use serde::Serialize;
 use crate::common::Size;
+use crate::js::Js;
 use crate::options::ChartConfig;
+use crate::plugin::{Dependencies, ScriptSource};
 use crate::serde::WithTypeAndSerializer;
 
+/// Chart.js date adapter, time scales don't work without one. Version 3 is the one for Chart.js 4.
+pub const DATE_ADAPTER_SCRIPT: &str = "https://cdn.jsdelivr.net/npm/chartjs-adapter-date-fns@3/dist/chartjs-adapter-date-fns.bundle.min.js";
+
+/// Luxon and its date adapter, used instead of the default one when a chart has a time zone.
+/// Formats are translated to luxon 3 tokens, version 1 of the adapter is the one for Chart.js 4.
+pub const LUXON_SCRIPT: &str = "https://cdn.jsdelivr.net/npm/luxon@3";
+pub const LUXON_ADAPTER_SCRIPT: &str = "https://cdn.jsdelivr.net/npm/chartjs-adapter-luxon@1";
+
 #[derive(TemplateSimple)]
 #[template(path = "one_page_chart.stpl")]
 pub struct OnePage<'a>{
     title: &'a str,
-    body: &'a str
+    body: Cow<'a, str>,
+    dependencies: Dependencies
 }
 
 impl<'a> OnePage<'a> {
     pub fn new(title: &'a str, body: &'a str) -> Self {
         Self {
             title,
-            body
+            body: Cow::Borrowed(body),
+            dependencies: Dependencies::default()
+        }
+    }
+
+    /// Adds a script to load after Chart.js, each script is loaded once
+    pub fn with_script<S: Into<ScriptSource>>(mut self, script: S) -> Self {
+        self.dependencies.add_script(script);
+        self
+    }
+
+    /// Renders the chart at the end of the body, the scripts it needs are loaded once for all charts
+    pub fn add_chart<X,Y>(mut self, mut chart: Chart<X,Y>) -> Result<Self, RenderError>
+    where X: WithTypeAndSerializer+Serialize, Y: WithTypeAndSerializer+Serialize
+    {
+        if let Some(dependencies) = chart.embedded.take() {
+            self.dependencies.merge(&dependencies);
         }
+        let html = chart.render_once()?;
+        self.body.to_mut().push_str(&html);
+        Ok(self)
     }
 }
 
@@ -30,17 +61,47 @@ where X: WithTypeAndSerializer+Serialize, Y: WithTypeAndSerializer+Serialize
     chart_target_id: String,
     width: Size,
     height: Size,
-    options: ChartConfig<X,Y>
+    options: ChartConfig<X,Y>,
+    ///dependencies rendered along with the chart, taken over by the page in `OnePage::add_chart`
+    embedded: Option<Dependencies>
 }
 
 impl<X,Y> Chart<X,Y> where X: WithTypeAndSerializer+Serialize, Y: WithTypeAndSerializer+Serialize
 {
     pub fn new(chart_target_id: String, width: Size, height: Size, options: ChartConfig<X,Y>) -> Self {
-        Self {
+        let mut chart = Self {
             chart_target_id,
             width,
             height,
-            options
+            options,
+            embedded: None
+        };
+        chart.embedded = Some(chart.dependencies()).filter(|d| !d.is_empty());
+        chart
+    }
+
+    /// Scripts the chart needs on the page besides Chart.js itself, with the plugin registration code.
+    ///
+    /// A chart rendered on its own loads them right before its script, so it works in any page with Chart.js.
+    /// Pages with several charts should add them with `OnePage::add_chart`, or use `without_dependencies`
+    /// and include these once, so each script is loaded and each plugin registered only once.
+    pub fn dependencies(&self) -> Dependencies {
+        let mut dependencies = Dependencies::default();
+        if self.options.options.uses_time_scale() {
+            if self.options.options.time_zone.is_some() {
+                dependencies.add_script(LUXON_SCRIPT);
+                dependencies.add_script(LUXON_ADAPTER_SCRIPT);
+            } else {
+                dependencies.add_script(DATE_ADAPTER_SCRIPT);
+            }
         }
+        self.options.plugin_dependencies(&mut dependencies);
+        dependencies
     }
-}
\ No newline at end of file
+
+    /// Renders the chart without its dependencies, for pages that load them already
+    pub fn without_dependencies(mut self) -> Self {
+        self.embedded = None;
+        self
+    }
+}
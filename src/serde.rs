@@ -1,6 +1,54 @@
-use std::time::{Instant, SystemTime};
+use std::cell::RefCell;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime};
 use serde::{Serialize, Serializer};
-use crate::ScaleType;
+use serde::ser::Error;
+use crate::format::NumberFormat;
+#[cfg(any(feature = "time_axis", feature = "chrono_axis", feature = "jiff_axis"))]
+use crate::time_zone::ChartTimeZone;
+use crate::{NonFinitePolicy, ScaleType};
+#[cfg(feature = "decimal")]
+use crate::decimal::DecimalRounding;
+
+/// Chart-level settings values are serialized with.
+///
+/// `SerializeFormat` implementations are static, so the chart being rendered sets these
+/// for the current thread with a [`SettingsGuard`].
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SerializeSettings {
+    #[cfg(any(feature = "time_axis", feature = "chrono_axis", feature = "jiff_axis"))]
+    pub(crate) naive_zone: ChartTimeZone,
+    pub(crate) non_finite: NonFinitePolicy,
+    ///the `y` index axis is used, values of `X` go to the vertical scale
+    pub(crate) transposed: bool,
+    #[cfg(feature = "decimal")]
+    pub(crate) decimal_rounding: Option<DecimalRounding>
+}
+
+thread_local! {
+    static SETTINGS: RefCell<Option<SerializeSettings>> = const { RefCell::new(None) };
+}
+
+/// Settings of the chart being rendered on this thread, defaults outside of rendering
+pub(crate) fn settings() -> SerializeSettings {
+    SETTINGS.with(|s| *s.borrow()).unwrap_or_default()
+}
+
+/// Sets serialize settings for the current thread, previous ones are restored on drop
+pub(crate) struct SettingsGuard(Option<SerializeSettings>);
+
+impl SettingsGuard {
+    pub(crate) fn set(settings: SerializeSettings) -> Self {
+        SettingsGuard(SETTINGS.with(|s| s.replace(Some(settings))))
+    }
+}
+
+impl Drop for SettingsGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        SETTINGS.with(|s| *s.borrow_mut() = previous);
+    }
+}
 
 #[derive(Debug,Clone)]
 pub struct ValueSerializeWrapper<T: WithTypeAndSerializer>(pub(crate) T);
@@ -23,6 +71,16 @@ impl<T: WithTypeAndSerializer> Serialize for ValueSerializeWrapper<T> {
 pub trait WithTypeAndSerializer {
     fn scale_type()->ScaleType;
 
+    /// Tick label format used by default for an axis of this type
+    fn tick_format() -> Option<NumberFormat> {
+        None
+    }
+
+    /// False for values JSON can't express, like NaN, see `NonFinitePolicy`
+    fn is_finite(&self) -> bool {
+        true
+    }
+
     type Serialization: SerializeFormat<Self>
     where
         Self: Sized;
@@ -79,33 +137,170 @@ macro_rules! impl_scale_type {
 
 
 
-impl_scale_type_default!(Linear for u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64);
+impl_scale_type_default!(Linear for u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
 impl_scale_type_default!(Category for &str String);
 impl_scale_type!(Time for SystemTime Instant);
 
 
+impl WithTypeAndSerializer for Duration {
+    fn scale_type() -> ScaleType {
+        ScaleType::Linear
+    }
+
+    fn tick_format() -> Option<NumberFormat> {
+        Some(NumberFormat::duration())
+    }
+
+    type Serialization = Duration;
+}
+
+macro_rules! impl_float {
+    ($($t:ty)*) => ($(
+        impl WithTypeAndSerializer for $t {
+            fn scale_type() -> ScaleType {
+                ScaleType::Linear
+            }
+
+            fn is_finite(&self) -> bool {
+                <$t>::is_finite(*self)
+            }
+
+            type Serialization = $t;
+        }
+
+        impl SerializeFormat<$t> for $t {
+            fn serialize<S>(value: &$t, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer
+            {
+                if value.is_finite() {
+                    return value.serialize(serializer);
+                }
+                match settings().non_finite {
+                    NonFinitePolicy::Error => Err(S::Error::custom(format!("non-finite value {} in chart data", value))),
+                    //skipped points never get here, a stray value still has to be valid JSON
+                    NonFinitePolicy::Null | NonFinitePolicy::Skip => serializer.serialize_none()
+                }
+            }
+        }
+    )*)
+}
+
+impl_float!(f32 f64);
+
+
+/// Missing value, `None` is sent as `null` and shows up as a gap (see `SpanGaps`)
+impl<T: WithTypeAndSerializer> WithTypeAndSerializer for Option<T> {
+    fn scale_type() -> ScaleType {
+        T::scale_type()
+    }
+
+    fn tick_format() -> Option<NumberFormat> {
+        T::tick_format()
+    }
+
+    fn is_finite(&self) -> bool {
+        self.as_ref().is_none_or(T::is_finite)
+    }
+
+    type Serialization = OptionSerialisation;
+}
+
+pub struct OptionSerialisation;
+
+impl<T: WithTypeAndSerializer> SerializeFormat<Option<T>> for OptionSerialisation {
+    fn serialize<S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        match value {
+            Some(value) => T::Serialization::serialize(value, serializer),
+            None => serializer.serialize_none()
+        }
+    }
+}
+
+/// Chart.js works with milliseconds, for both time scales and durations
+fn as_millis_f64(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Milliseconds since the Unix epoch, what Chart.js time scales expect.
+///
+/// Earlier versions wrote seconds, so charts with `SystemTime` values now serialize 1000 times larger numbers,
+/// custom JS reading these values has to treat them as milliseconds.
 impl SerializeFormat<SystemTime> for SystemTime{
     fn serialize<S>(value: &SystemTime, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer
     {
-        let duration = value.duration_since(SystemTime::UNIX_EPOCH).unwrap();
-        let seconds = duration.as_secs();
-        let nanos = duration.subsec_nanos();
-        let seconds_f64 = seconds as f64 + nanos as f64 / 1_000_000_000.0;
-        serializer.serialize_f64(seconds_f64)
+        let millis = match value.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(after) => as_millis_f64(after),
+            Err(before) => -as_millis_f64(before.duration())
+        };
+        serializer.serialize_f64(millis)
     }
 }
 
+/// Wall clock time of an `Instant`, taken once so the same instant is always placed at the same time
+fn instant_clock() -> (SystemTime, Instant) {
+    static CLOCK: OnceLock<(SystemTime, Instant)> = OnceLock::new();
+    *CLOCK.get_or_init(|| (SystemTime::now(), Instant::now()))
+}
+
+/// Instant is placed on the wall clock, relative to a reference taken the first time an instant is serialized,
+/// so rendering the same instants again gives the same output.
+///
+/// To plot instants relative to each other use `ChartConfig::add_instant_series` on a `Duration` axis.
 impl SerializeFormat<Instant> for Instant{
     fn serialize<S>(value: &Instant, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer
     {
-        let duration = value.elapsed();
-        let seconds = duration.as_secs();
-        let nanos = duration.subsec_nanos();
-        let seconds_f64 = seconds as f64 + nanos as f64 / 1_000_000_000.0;
-        serializer.serialize_f64(seconds_f64)
+        let (clock, clock_instant) = instant_clock();
+        let wall_clock = if *value <= clock_instant {
+            clock - clock_instant.duration_since(*value)
+        } else {
+            clock + value.duration_since(clock_instant)
+        };
+        <SystemTime as SerializeFormat<SystemTime>>::serialize(&wall_clock, serializer)
+    }
+}
+
+impl SerializeFormat<Duration> for Duration{
+    fn serialize<S>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        serializer.serialize_f64(as_millis_f64(*value))
     }
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instant_test() {
+        let earlier = Instant::now();
+        let later = earlier + Duration::from_millis(1500);
+        let earlier_ms: f64 = serde_json::to_string(&ValueSerializeWrapper(earlier)).unwrap().parse().unwrap();
+        let later_ms: f64 = serde_json::to_string(&ValueSerializeWrapper(later)).unwrap().parse().unwrap();
+        assert!((later_ms - earlier_ms - 1500.0).abs() < 1.0);
+        //the same instant is placed at the same time in later renders
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(serde_json::to_string(&ValueSerializeWrapper(earlier)).unwrap().parse::<f64>().unwrap(), earlier_ms);
+        assert_eq!(serde_json::to_string(&ValueSerializeWrapper(Duration::from_micros(1500))).unwrap(), "1.5");
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(2);
+        assert_eq!(serde_json::to_string(&ValueSerializeWrapper(time)).unwrap(), "2000.0");
+    }
+
+    #[test]
+    fn non_finite_test() {
+        assert_eq!(serde_json::to_string(&ValueSerializeWrapper(Some(f64::NAN))).unwrap(), "null");
+        assert_eq!(serde_json::to_string(&ValueSerializeWrapper(None::<u32>)).unwrap(), "null");
+        let _guard = SettingsGuard::set(SerializeSettings{ non_finite: NonFinitePolicy::Error, ..SerializeSettings::default() });
+        assert!(serde_json::to_string(&ValueSerializeWrapper(f32::INFINITY)).is_err());
+    }
+}
@@ -2,9 +2,9 @@ use serde::{Serialize, Serializer};
 use serde::ser::Error;
 use crate::{impl_scale_type, ScaleType};
 use crate::serde::{SerializeFormat, WithTypeAndSerializer};
-use time::{OffsetDateTime, UtcDateTime};
+use crate::time_zone::naive_zone;
+use time::{OffsetDateTime, UtcDateTime, UtcOffset};
 use time::format_description::well_known::Rfc3339;
-use time::macros::format_description;
 
 impl_scale_type!(Category for time::Month time::Weekday time::Time);
 impl_scale_type!(Time for OffsetDateTime time::PrimitiveDateTime UtcDateTime time::Date);
@@ -43,13 +43,18 @@ impl SerializeFormat<OffsetDateTime> for OffsetDateTime{
 }
 
 
+/// Naive date-time is put in the chart's naive time zone, see `ChartConfig::with_naive_time_zone`
 impl SerializeFormat<time::PrimitiveDateTime> for time::PrimitiveDateTime{
     fn serialize<S>(value: &time::PrimitiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer
     {
-
-        let str = value.format(&Rfc3339).map_err(|err| Error::custom(err.to_string()))?;
+        let offset = naive_zone().offset_of_local(
+            (value.year(), value.month() as u32, value.day() as u32),
+            (value.hour() as u32, value.minute() as u32, value.second() as u32)
+        );
+        let offset = UtcOffset::from_whole_seconds(offset).map_err(|err| Error::custom(err.to_string()))?;
+        let str = value.assume_offset(offset).format(&Rfc3339).map_err(|err| Error::custom(err.to_string()))?;
         //serialize to rfc string
         serializer.serialize_str(str.as_str())
     }
@@ -69,14 +74,13 @@ impl SerializeFormat<UtcDateTime> for UtcDateTime{
 }
 
 
+/// Date is the midnight of the day in the chart's naive time zone
 impl SerializeFormat<time::Date> for time::Date{
     fn serialize<S>(value: &time::Date, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer
     {
-        let str = value.format(&Rfc3339).map_err(|err| Error::custom(err.to_string()))?;
-        //serialize to rfc string
-        serializer.serialize_str(str.as_str())
+        <time::PrimitiveDateTime as SerializeFormat<time::PrimitiveDateTime>>::serialize(&value.midnight(), serializer)
     }
 }
 
@@ -86,8 +90,22 @@ impl SerializeFormat<time::Time> for time::Time{
     where
         S: Serializer
     {
-        let str = value.format(&Rfc3339).map_err(|err| Error::custom(err.to_string()))?;
-        //serialize to rfc string
-        serializer.serialize_str(str.as_str())
+        format!("{:02}:{:02}:{:02}", value.hour(), value.minute(), value.second()).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::to_string;
+    use time::macros::datetime;
+    use crate::serde::{SerializeSettings, SettingsGuard, ValueSerializeWrapper};
+    use crate::time_zone::ChartTimeZone;
+
+    #[test]
+    fn primitive_date_time_test() {
+        //used to fail, there is no offset to format as RFC 3339
+        assert_eq!(to_string(&ValueSerializeWrapper(datetime!(2025-01-01 10:00))).unwrap(), r#""2025-01-01T10:00:00Z""#);
+        let _guard = SettingsGuard::set(SerializeSettings{ naive_zone: ChartTimeZone::fixed_hours(3), ..SerializeSettings::default() });
+        assert_eq!(to_string(&ValueSerializeWrapper(datetime!(2025-01-01 10:00).date())).unwrap(), r#""2025-01-01T00:00:00+03:00""#);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,168 @@
+#[cfg(any(feature = "time_axis", feature = "chrono_axis", feature = "jiff_axis"))]
+use crate::serde::settings;
+
+/// Time zone of a chart.
+///
+/// Used in two ways: as the zone ticks and tooltips are displayed in (see `ChartConfig::with_time_zone`)
+/// and as the zone naive date-times are assumed to be in (see `ChartConfig::with_naive_time_zone`).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ChartTimeZone {
+    #[default]
+    Utc,
+    ///offset in seconds east of UTC
+    FixedOffset(i32),
+    ///IANA zone, like Europe/Berlin
+    #[cfg(feature = "chrono-tz")]
+    Iana(chrono_tz::Tz)
+}
+
+#[cfg(feature = "chrono-tz")]
+impl From<chrono_tz::Tz> for ChartTimeZone {
+    fn from(value: chrono_tz::Tz) -> Self {
+        ChartTimeZone::Iana(value)
+    }
+}
+
+impl ChartTimeZone {
+
+    pub fn fixed_hours(hours: i32) -> Self {
+        ChartTimeZone::FixedOffset(hours * 3600)
+    }
+
+    /// Zone in the syntax of the luxon date adapter
+    pub(crate) fn luxon_zone(&self) -> String {
+        match self {
+            ChartTimeZone::Utc => "utc".to_string(),
+            ChartTimeZone::FixedOffset(0) => "utc".to_string(),
+            ChartTimeZone::FixedOffset(offset) => {
+                let sign = if *offset < 0 { '-' } else { '+' };
+                let minutes = offset.unsigned_abs() / 60;
+                format!("UTC{}{}:{:02}", sign, minutes / 60, minutes % 60)
+            }
+            #[cfg(feature = "chrono-tz")]
+            ChartTimeZone::Iana(tz) => tz.name().to_string()
+        }
+    }
+
+    /// Offset in seconds east of UTC of a wall-clock time in this zone.
+    ///
+    /// Ambiguous times (DST end) take the earlier offset, times skipped by DST start take the offset before the gap.
+    #[cfg(any(feature = "time_axis", feature = "chrono_axis", feature = "jiff_axis"))]
+    pub(crate) fn offset_of_local(&self, date: (i32, u32, u32), time: (u32, u32, u32)) -> i32 {
+        //only IANA zones depend on the date and time
+        match (self, date, time) {
+            (ChartTimeZone::Utc, ..) => 0,
+            (ChartTimeZone::FixedOffset(offset), ..) => *offset,
+            #[cfg(feature = "chrono-tz")]
+            (ChartTimeZone::Iana(tz), date, time) => {
+                use chrono::{NaiveDate, Offset, TimeZone};
+                let Some(local) = NaiveDate::from_ymd_opt(date.0, date.1, date.2)
+                    .and_then(|d| d.and_hms_opt(time.0, time.1, time.2)) else {
+                    return 0;
+                };
+                tz.offset_from_local_datetime(&local)
+                    .earliest()
+                    .unwrap_or_else(|| tz.offset_from_utc_datetime(&local))
+                    .fix()
+                    .local_minus_utc()
+            }
+        }
+    }
+}
+
+/// Zone naive date-times are assumed to be in while they are serialized, UTC unless a chart is being rendered
+#[cfg(any(feature = "time_axis", feature = "chrono_axis", feature = "jiff_axis"))]
+pub(crate) fn naive_zone() -> ChartTimeZone {
+    settings().naive_zone
+}
+
+
+/// Date format of the date-fns adapter in the syntax of the luxon one, tokens luxon has no equivalent for are an error
+pub(crate) fn luxon_format(date_fns: &str) -> Result<String, String> {
+    let chars: Vec<char> = date_fns.chars().collect();
+    let mut luxon = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\'' {
+            let end = chars[i + 1..].iter().position(|&q| q == '\'').map(|p| i + 1 + p)
+                .ok_or_else(|| format!("unterminated quote in date format {:?}", date_fns))?;
+            if end == i + 1 || chars.get(end + 1) == Some(&'\'') {
+                return Err(format!("quote characters in date format {:?} can't be translated to luxon", date_fns));
+            }
+            luxon.extend(&chars[i..=end]);
+            i = end + 1;
+            continue;
+        }
+        if !c.is_ascii_alphabetic() {
+            luxon.push(c);
+            i += 1;
+            continue;
+        }
+        let len = chars[i..].iter().take_while(|&&t| t == c).count();
+        let token: String = chars[i..i + len].iter().collect();
+        i += len;
+        //date-fns' localized date followed by localized time is luxon's localized date-time
+        if c == 'P' && len <= 4 && chars[i..].iter().take_while(|&&t| t == 'p').count() == len {
+            luxon.push_str(&"f".repeat(len));
+            i += len;
+            continue;
+        }
+        let translated = match token.as_str() {
+            "y" | "yy" | "yyyy" | "d" | "dd" | "H" | "HH" | "h" | "hh" | "m" | "mm" | "s" | "ss" | "SSS" | "q" | "qq"
+                | "M" | "MM" | "MMM" | "MMMM" | "MMMMM" | "L" | "LL" | "LLL" | "LLLL" | "LLLLL" | "EEEE" | "EEEEE" => token.as_str(),
+            "E" | "EE" | "EEE" => "EEE",
+            "a" | "aa" | "aaa" => "a",
+            "Q" => "q",
+            "QQ" => "qq",
+            "I" => "W",
+            "II" => "WW",
+            "D" => "o",
+            "DDD" => "ooo",
+            "XXX" | "xxx" => "ZZ",
+            "XX" | "xx" => "ZZZ",
+            "P" => "D",
+            "PP" => "DD",
+            "PPP" => "DDD",
+            "PPPP" => "DDDD",
+            "p" => "t",
+            "pp" => "tt",
+            "ppp" => "ttt",
+            "pppp" => "tttt",
+            _ => return Err(format!("date format token {:?} of {:?} has no luxon equivalent", token, date_fns))
+        };
+        luxon.push_str(translated);
+    }
+    Ok(luxon)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zone_test() {
+        assert_eq!(ChartTimeZone::FixedOffset(-(4 * 3600 + 1800)).luxon_zone(), "UTC-4:30");
+    }
+
+    #[test]
+    fn luxon_format_test() {
+        assert_eq!(luxon_format("EEE, MMM d yyyy HH:mm").unwrap(), "EEE, MMM d yyyy HH:mm");
+        assert_eq!(luxon_format("'Q'Q yyyy, 'week' I").unwrap(), "'Q'q yyyy, 'week' W");
+        assert_eq!(luxon_format("PPpp").unwrap(), "ff");
+        assert_eq!(luxon_format("PP p").unwrap(), "DD t");
+        assert!(luxon_format("do MMM").is_err());
+        assert!(luxon_format("h 'o''clock'").is_err());
+    }
+
+    #[cfg(any(feature = "time_axis", feature = "chrono_axis", feature = "jiff_axis"))]
+    #[test]
+    fn naive_zone_test() {
+        use crate::serde::{SerializeSettings, SettingsGuard};
+        {
+            let _guard = SettingsGuard::set(SerializeSettings{ naive_zone: ChartTimeZone::fixed_hours(2), ..SerializeSettings::default() });
+            assert_eq!(naive_zone(), ChartTimeZone::FixedOffset(7200));
+        }
+        assert_eq!(naive_zone(), ChartTimeZone::Utc);
+    }
+}
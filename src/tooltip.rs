@@ -0,0 +1,335 @@
+use crate::common::{Color, Rgb, Size};
+use crate::format::NumberFormat;
+use crate::js::js_literal;
+use crate::options::{JsExpr, TooltipCallbacks};
+
+#[derive(Debug, Clone)]
+enum LinePart {
+    Text(String),
+    Field { key: String, format: Option<NumberFormat> },
+    Value(Option<NumberFormat>),
+    DatasetLabel
+}
+
+/// One tooltip line put together from text, point metadata and the point value.
+///
+/// A line with metadata fields is left out when none of them is set on the point.
+#[derive(Debug, Clone, Default)]
+pub struct TooltipLine {
+    parts: Vec<LinePart>
+}
+
+impl TooltipLine {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(mut self, text: &str) -> Self {
+        self.parts.push(LinePart::Text(text.to_string()));
+        self
+    }
+
+    ///metadata field of the point as it is
+    pub fn field(mut self, key: &str) -> Self {
+        self.parts.push(LinePart::Field { key: key.to_string(), format: None });
+        self
+    }
+
+    ///numeric metadata field of the point, formatted like axis ticks
+    pub fn formatted_field(mut self, key: &str, format: NumberFormat) -> Self {
+        self.parts.push(LinePart::Field { key: key.to_string(), format: Some(format) });
+        self
+    }
+
+    ///value of the point, formatted the same way as on its axis
+    pub fn value(mut self) -> Self {
+        self.parts.push(LinePart::Value(None));
+        self
+    }
+
+    pub fn formatted_value(mut self, format: NumberFormat) -> Self {
+        self.parts.push(LinePart::Value(Some(format)));
+        self
+    }
+
+    pub fn dataset_label(mut self) -> Self {
+        self.parts.push(LinePart::DatasetLabel);
+        self
+    }
+
+    /// JS expression of the line for the tooltip item `item`, `null` when the line is left out
+    fn js(&self) -> String {
+        let has_fields = self.parts.iter().any(|p| matches!(p, LinePart::Field { .. }));
+        let mut js = format!("(function(){{ let found = {}; let s = ''; ", !has_fields);
+        for part in &self.parts {
+            match part {
+                LinePart::Text(text) => js.push_str(&format!("s += {}; ", js_literal(text))),
+                LinePart::Field { key, format } => js.push_str(&format!(
+                    "{{ const v = m[{}]; if (v !== undefined && v !== null) {{ found = true; s += {}; }} }} ",
+                    js_literal(key),
+                    formatted("v", format.as_ref())
+                )),
+                LinePart::Value(None) => js.push_str("s += item.formattedValue; "),
+                //the value is on the axis opposite to the index one, which a dataset can set for itself
+                LinePart::Value(Some(format)) => js.push_str(&format!(
+                    "s += {}; ",
+                    formatted("((item.dataset.indexAxis || item.chart.options.indexAxis) === 'y' ? item.parsed.x : item.parsed.y)", Some(format))
+                )),
+                LinePart::DatasetLabel => js.push_str("s += item.dataset.label || ''; ")
+            }
+        }
+        js.push_str("return found ? s : null; })()");
+        js
+    }
+}
+
+fn formatted(value: &str, format: Option<&NumberFormat>) -> String {
+    match format {
+        Some(format) => format!("(function(value){{ {} }})({})", format.js_body("value"), value),
+        None => format!("String({})", value)
+    }
+}
+
+/// Tooltip title, body and footer built from point metadata, see `Tooltip::with_layout`
+/// and `PointMeta`.
+#[derive(Debug, Clone, Default)]
+pub struct TooltipLayout {
+    title: Option<TooltipLine>,
+    body: Vec<TooltipLine>,
+    footer: Vec<TooltipLine>
+}
+
+impl TooltipLayout {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///title from the first item under the pointer
+    pub fn with_title(mut self, line: TooltipLine) -> Self {
+        self.title = Some(line);
+        self
+    }
+
+    ///adds a body line, shown for every item under the pointer
+    pub fn with_line(mut self, line: TooltipLine) -> Self {
+        self.body.push(line);
+        self
+    }
+
+    ///adds a footer line, from the first item under the pointer
+    pub fn with_footer(mut self, line: TooltipLine) -> Self {
+        self.footer.push(line);
+        self
+    }
+
+    fn lines_js(lines: &[TooltipLine]) -> String {
+        let lines: Vec<String> = lines.iter().map(TooltipLine::js).collect();
+        format!(
+            "const m = (item.raw && item.raw.meta) || {{}}; return [{}].filter(function(l){{ return l !== null; }});",
+            lines.join(", ")
+        )
+    }
+
+    /// Replaces the callbacks the layout has lines for
+    pub(crate) fn apply(&self, callbacks: &mut TooltipCallbacks) {
+        if let Some(title) = &self.title {
+            callbacks.title = Some(JsExpr::new(format!(
+                "function(items){{ const item = items[0]; if (!item) {{ return ''; }} const m = (item.raw && item.raw.meta) || {{}}; return {} || ''; }}",
+                title.js()
+            )));
+        }
+        if !self.body.is_empty() {
+            callbacks.label = Some(JsExpr::new(format!("function(item){{ {} }}", Self::lines_js(&self.body))));
+        }
+        if !self.footer.is_empty() {
+            callbacks.footer = Some(JsExpr::new(format!(
+                "function(items){{ const item = items[0]; if (!item) {{ return []; }} {} }}",
+                Self::lines_js(&self.footer)
+            )));
+        }
+    }
+}
+
+
+/// Positions the HTML tooltip next to the caret, it stays open while hovered so its links can be followed
+const EXTERNAL_FN: &str = "function(context){ \
+    const chart = context.chart; const tooltip = context.tooltip; \
+    const el = document.getElementById(chart.canvas.id + '-tooltip'); \
+    if (!el) { return; } \
+    if (!el.dataset.bound) { \
+        el.dataset.bound = '1'; \
+        el.addEventListener('mouseenter', function(){ el.dataset.hover = '1'; }); \
+        el.addEventListener('mouseleave', function(){ delete el.dataset.hover; el.style.display = 'none'; }); \
+    } \
+    clearTimeout(el.hideTimer); \
+    if (tooltip.opacity === 0) { \
+        el.hideTimer = setTimeout(function(){ if (!el.dataset.hover) { el.style.display = 'none'; } }, 300); \
+        return; \
+    } \
+    const html = tooltip.dataPoints.map(function(p){ return p.raw && p.raw.tooltipHtml; }).filter(function(h){ return h; }); \
+    if (html.length) { el.innerHTML = html.join(''); } else { \
+        el.textContent = ''; \
+        const add = function(text, bold){ const d = document.createElement('div'); d.textContent = text; if (bold) { d.style.fontWeight = 'bold'; } el.appendChild(d); }; \
+        (tooltip.title || []).forEach(function(l){ add(l, true); }); \
+        tooltip.body.forEach(function(b){ b.lines.forEach(function(l){ add(l, false); }); }); \
+        (tooltip.footer || []).forEach(function(l){ add(l, false); }); \
+    } \
+    el.style.display = 'block'; \
+    let left = tooltip.caretX + 12; \
+    if (left + el.offsetWidth > chart.width) { left = Math.max(0, tooltip.caretX - el.offsetWidth - 12); } \
+    el.style.left = left + 'px'; \
+    el.style.top = Math.max(0, Math.min(tooltip.caretY - el.offsetHeight / 2, chart.height - el.offsetHeight)) + 'px'; \
+}";
+
+/// HTML tooltip in place of the canvas one, so it can have links and tables, see `Tooltip::with_external`.
+///
+/// Points show their `PointMeta::with_tooltip_template`, points without one the usual title, body and footer.
+#[derive(Debug, Clone)]
+pub struct ExternalTooltip {
+    pub(crate) class: Option<String>,
+    background_color: Color,
+    text_color: Color,
+    max_width: Size
+}
+
+impl Default for ExternalTooltip {
+    fn default() -> Self {
+        Self {
+            class: None,
+            background_color: Rgb(0, 0, 0).with_alpha(0.8),
+            text_color: Rgb(255, 255, 255).into(),
+            max_width: Size::pixels(320)
+        }
+    }
+}
+
+impl ExternalTooltip {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///CSS class of the container, styled by the page then, colors and width are left out
+    pub fn with_class(mut self, class: &str) -> Self {
+        self.class = Some(class.to_string());
+        self
+    }
+
+    pub fn with_background_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.background_color = color.into();
+        self
+    }
+
+    pub fn with_text_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.text_color = color.into();
+        self
+    }
+
+    pub fn with_max_width(mut self, width: Size) -> Self {
+        self.max_width = width;
+        self
+    }
+
+    /// Inline style of the container
+    pub(crate) fn style(&self) -> String {
+        let mut style = "display: none; position: absolute; z-index: 10; pointer-events: auto;".to_string();
+        if self.class.is_none() {
+            style.push_str(&format!(
+                " background: {}; color: {}; max-width: {}; border-radius: 4px; padding: 6px 8px; font: 12px sans-serif;",
+                self.background_color, self.text_color, self.max_width
+            ));
+        }
+        style
+    }
+
+    pub(crate) fn external_fn() -> JsExpr {
+        JsExpr::new(EXTERNAL_FN)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{DataPointWithMeta, PointMeta};
+    use crate::js::to_js;
+    use crate::options::{Tooltip, TooltipMode, TooltipPosition};
+    use sailfish::TemplateSimple;
+
+    #[derive(TemplateSimple)]
+    #[template(path = "../tests/templates/incident_tooltip.stpl")]
+    struct IncidentTooltip<'a> {
+        id: u32,
+        title: &'a str
+    }
+
+    #[test]
+    fn tooltip_layout_test() {
+        let layout = TooltipLayout::new()
+            .with_title(TooltipLine::new().text("Host ").field("host"))
+            .with_line(TooltipLine::new().dataset_label().text(": ").value())
+            .with_footer(TooltipLine::new().text("</script>").field("commit"));
+        let mut callbacks = TooltipCallbacks::default();
+        layout.apply(&mut callbacks);
+        assert!(callbacks.title.unwrap().as_str().contains(r#"s += "Host "; { const v = m["host"]; if (v !== undefined && v !== null) { found = true; s += String(v); } }"#));
+        assert!(callbacks.label.unwrap().as_str().contains("(function(){ let found = true; let s = ''; s += item.dataset.label || ''; s += \": \"; s += item.formattedValue; return found ? s : null; })()"));
+        assert!(callbacks.footer.unwrap().as_str().contains(r#"s += "<\/script>";"#));
+        let mut callbacks = TooltipCallbacks::default();
+        TooltipLayout::new().with_line(TooltipLine::new().formatted_value(crate::format::NumberFormat::number())).apply(&mut callbacks);
+        assert!(callbacks.label.unwrap().as_str().contains("(item.dataset.indexAxis || item.chart.options.indexAxis) === 'y' ? item.parsed.x : item.parsed.y"));
+
+        let meta = PointMeta::new().with("host", "db-1").unwrap().with("duration", &1250).unwrap();
+        assert_eq!(meta.get("duration"), Some(&serde_json::json!(1250)));
+        assert!(PointMeta::from_serialize(&[1, 2]).is_err());
+        let meta = meta.with_url("/hosts/db-1");
+        let json = serde_json::to_string(&meta).unwrap();
+        assert_eq!(json, r#"{"duration":1250,"host":"db-1","url":"/hosts/db-1"}"#);
+        assert_eq!(serde_json::from_str::<PointMeta>(&json).unwrap(), meta);
+    }
+
+    #[test]
+    fn external_tooltip_test() {
+        let tooltip = Tooltip::default()
+            .with_callbacks(TooltipCallbacks { title: None, ..TooltipCallbacks::default() })
+            .with_external(ExternalTooltip::new().with_max_width(Size::pixels(200)));
+        let js = to_js(&tooltip).unwrap();
+        assert!(js.starts_with(r#"{"enabled":false,"external":function(context){ const chart = context.chart;"#));
+        assert!(js.ends_with(r#"},"callbacks":{}}"#));
+        assert_eq!(
+            tooltip.html.unwrap().style(),
+            "display: none; position: absolute; z-index: 10; pointer-events: auto; \
+             background: rgba(0, 0, 0, 0.8); color: rgb(255, 255, 255); max-width: 200px; border-radius: 4px; padding: 6px 8px; font: 12px sans-serif;"
+        );
+        let point: DataPointWithMeta<f64, f64> = (1.0, 2.0, PointMeta::new().with_tooltip_html_unescaped("<a href=\"/b/1\">#1</a>")).into();
+        assert_eq!(to_js(&point).unwrap(), r#"{"x":1.0,"y":2.0,"meta":{},"tooltipHtml":"<a href=\"/b/1\">#1<\/a>"}"#);
+
+        let incident = IncidentTooltip { id: 7, title: "<img src=x onerror=alert(1)>" };
+        let meta = PointMeta::new().with_tooltip_template(incident).unwrap();
+        let point: DataPointWithMeta<f64, f64> = (1.0, 2.0, meta).into();
+        assert!(to_js(&point).unwrap().contains(r#"#7<\/a><\/td><\/tr><tr><th>Title<\/th><td>&lt;img src=x onerror=alert(1)&gt;<\/td>"#));
+        let point: DataPointWithMeta<f64, f64> = (1.0, 2.0, PointMeta::new().with_tooltip_html("<b>1 & 2</b>")).into();
+        assert!(to_js(&point).unwrap().contains(r#""tooltipHtml":"&lt;b&gt;1 &amp; 2&lt;/b&gt;""#));
+    }
+
+    #[test]
+    fn tooltip_test() {
+        let tooltip = Tooltip::default()
+            .with_mode(TooltipMode::X)
+            .with_intersect(false)
+            .with_position(TooltipPosition::Nearest)
+            .with_footer_color(Rgb(0, 0, 0))
+            .with_item_sort(JsExpr::new("function(a, b){ return b.raw.y - a.raw.y; }"))
+            .with_callbacks(TooltipCallbacks{ title: None, ..TooltipCallbacks::default() });
+        assert_eq!(
+            to_js(&tooltip).unwrap(),
+            r#"{"enabled":true,"mode":"x","intersect":false,"position":"nearest","footerColor":"rgb(0, 0, 0)","itemSort":function(a, b){ return b.raw.y - a.raw.y; },"callbacks":{}}"#
+        );
+        #[allow(deprecated)]
+        let average = Tooltip::default().with_mode(TooltipMode::Average);
+        assert_eq!(average.mode, None);
+        assert_eq!(average.position, Some(TooltipPosition::Average));
+        assert_eq!(serde_json::from_str::<TooltipMode>(r#""index""#).unwrap(), TooltipMode::Index);
+    }
+}
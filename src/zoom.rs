@@ -0,0 +1,300 @@
+use std::borrow::Cow;
+use serde::{Serialize, Serializer};
+use serde::ser::SerializeMap;
+use crate::common::Color;
+use crate::plugin::{ChartPlugin, ScriptSource};
+use crate::serde::{settings, ValueSerializeWrapper, WithTypeAndSerializer};
+
+/// Hammer.js, chartjs-plugin-zoom needs it for panning and pinch zoom
+pub const HAMMER_SCRIPT: &str = "https://cdn.jsdelivr.net/npm/hammerjs@2.0.8";
+/// chartjs-plugin-zoom, version 2 is the one for Chart.js 4
+pub const ZOOM_SCRIPT: &str = "https://cdn.jsdelivr.net/npm/chartjs-plugin-zoom@2";
+
+/// chartjs-plugin-zoom along with Hammer.js, added by charts that have zoom
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ZoomPlugin;
+
+impl ChartPlugin for ZoomPlugin {
+    fn options_key(&self) -> Cow<'static, str> {
+        Cow::Borrowed("zoom")
+    }
+
+    fn scripts(&self) -> Vec<ScriptSource> {
+        vec![HAMMER_SCRIPT.into(), ZOOM_SCRIPT.into()]
+    }
+
+    fn registration(&self) -> Option<Cow<'static, str>> {
+        Some(Cow::Borrowed("if (window.ChartZoom) { Chart.register(window.ChartZoom); }"))
+    }
+}
+
+/// Axes zoom or pan applies to, by value type: `X` is the axis of the `X` values even when it is vertical
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZoomMode {
+    X,
+    Y,
+    XY
+}
+
+impl Serialize for ZoomMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let mode = match (self, settings().transposed) {
+            (ZoomMode::XY, _) => "xy",
+            (ZoomMode::X, false) | (ZoomMode::Y, true) => "x",
+            (ZoomMode::Y, false) | (ZoomMode::X, true) => "y"
+        };
+        serializer.serialize_str(mode)
+    }
+}
+
+/// Modifier key that has to be held for an interaction
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ModifierKey {
+    Ctrl,
+    Alt,
+    Shift,
+    Meta
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+struct Toggle {
+    enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modifier_key: Option<ModifierKey>
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct PanOptions {
+    enabled: bool,
+    mode: ZoomMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modifier_key: Option<ModifierKey>
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DragOptions {
+    enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    border_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modifier_key: Option<ModifierKey>
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ZoomOptions {
+    mode: ZoomMode,
+    wheel: Toggle,
+    pinch: Toggle,
+    drag: DragOptions
+}
+
+impl Default for ZoomOptions {
+    fn default() -> Self {
+        Self {
+            mode: ZoomMode::XY,
+            wheel: Toggle::default(),
+            pinch: Toggle::default(),
+            drag: DragOptions {
+                enabled: false,
+                background_color: None,
+                border_color: None,
+                modifier_key: None
+            }
+        }
+    }
+}
+
+/// Bound of how far an axis can be zoomed out or panned
+#[derive(Debug, Clone)]
+pub enum LimitValue<T: WithTypeAndSerializer> {
+    Value(ValueSerializeWrapper<T>),
+    ///where the axis was before any zoom or pan
+    Original
+}
+
+impl<T: WithTypeAndSerializer> Serialize for LimitValue<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        match self {
+            LimitValue::Value(value) => value.serialize(serializer),
+            LimitValue::Original => serializer.serialize_str("original")
+        }
+    }
+}
+
+impl<T: WithTypeAndSerializer> LimitValue<T> {
+    pub fn value(value: T) -> Self {
+        LimitValue::Value(value.into())
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaleLimits<T: WithTypeAndSerializer> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min: Option<LimitValue<T>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max: Option<LimitValue<T>>,
+    ///smallest range the axis can be zoomed in to, in axis units (milliseconds for time scales)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_range: Option<f64>
+}
+
+impl<T: WithTypeAndSerializer> Default for ScaleLimits<T> {
+    fn default() -> Self {
+        Self { min: None, max: None, min_range: None }
+    }
+}
+
+impl<T: WithTypeAndSerializer> ScaleLimits<T> {
+
+    pub fn with_min(mut self, min: LimitValue<T>) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    pub fn with_max(mut self, max: LimitValue<T>) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn with_min_range(mut self, range: f64) -> Self {
+        self.min_range = Some(range);
+        self
+    }
+}
+
+/// Limits keyed by scale id, swapped along with the scales for the `y` index axis
+#[derive(Debug, Clone)]
+struct Limits<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> {
+    x: Option<ScaleLimits<X>>,
+    y: Option<ScaleLimits<Y>>
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> Limits<X, Y> {
+    fn is_empty(&self) -> bool {
+        self.x.is_none() && self.y.is_none()
+    }
+}
+
+impl<X, Y> Serialize for Limits<X, Y> where X: WithTypeAndSerializer + Serialize, Y: WithTypeAndSerializer + Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let (x_key, y_key) = if settings().transposed { ("y", "x") } else { ("x", "y") };
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(x) = &self.x {
+            map.serialize_entry(x_key, x)?;
+        }
+        if let Some(y) = &self.y {
+            map.serialize_entry(y_key, y)?;
+        }
+        map.end()
+    }
+}
+
+/// Zoom and pan through chartjs-plugin-zoom, everything is off until enabled
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoomConfig<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pan: Option<PanOptions>,
+    zoom: ZoomOptions,
+    #[serde(skip_serializing_if = "Limits::is_empty")]
+    limits: Limits<X, Y>,
+    #[serde(skip)]
+    pub(crate) reset_button: Option<String>
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> Default for ZoomConfig<X, Y> {
+    fn default() -> Self {
+        Self {
+            pan: None,
+            zoom: ZoomOptions::default(),
+            limits: Limits { x: None, y: None },
+            reset_button: None
+        }
+    }
+}
+
+impl<X: WithTypeAndSerializer, Y: WithTypeAndSerializer> ZoomConfig<X, Y> {
+
+    ///axes wheel, pinch and drag zoom apply to, both by default
+    pub fn with_mode(mut self, mode: ZoomMode) -> Self {
+        self.zoom.mode = mode;
+        self
+    }
+
+    pub fn with_wheel(mut self, modifier_key: Option<ModifierKey>) -> Self {
+        self.zoom.wheel = Toggle { enabled: true, modifier_key };
+        self
+    }
+
+    pub fn with_pinch(mut self) -> Self {
+        self.zoom.pinch.enabled = true;
+        self
+    }
+
+    ///zoom to a rectangle dragged with the mouse
+    pub fn with_drag(mut self, modifier_key: Option<ModifierKey>) -> Self {
+        self.zoom.drag.enabled = true;
+        self.zoom.drag.modifier_key = modifier_key;
+        self
+    }
+
+    ///colors of the rectangle shown while dragging
+    pub fn with_drag_colors<C: Into<Color>>(mut self, background: C, border: C) -> Self {
+        self.zoom.drag.background_color = Some(background.into());
+        self.zoom.drag.border_color = Some(border.into());
+        self
+    }
+
+    ///panning takes the same mouse drag as drag zoom, give one of them a modifier key when both are on
+    pub fn with_pan(mut self, mode: ZoomMode, modifier_key: Option<ModifierKey>) -> Self {
+        self.pan = Some(PanOptions { enabled: true, mode, modifier_key });
+        self
+    }
+
+    pub fn with_x_limits(mut self, limits: ScaleLimits<X>) -> Self {
+        self.limits.x = Some(limits);
+        self
+    }
+
+    pub fn with_y_limits(mut self, limits: ScaleLimits<Y>) -> Self {
+        self.limits.y = Some(limits);
+        self
+    }
+
+    ///button next to the chart that resets zoom and pan
+    pub fn with_reset_button(mut self, text: &str) -> Self {
+        self.reset_button = Some(text.to_string());
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::js::to_js;
+    use crate::serde::{SerializeSettings, SettingsGuard};
+
+    #[test]
+    fn zoom_test() {
+        let zoom = ZoomConfig::<f64, f64>::default()
+            .with_mode(ZoomMode::X)
+            .with_wheel(Some(ModifierKey::Ctrl))
+            .with_pan(ZoomMode::X, None)
+            .with_x_limits(ScaleLimits::default().with_min(LimitValue::Original).with_max(LimitValue::value(100.0)));
+        assert_eq!(
+            to_js(&zoom).unwrap(),
+            r#"{"pan":{"enabled":true,"mode":"x"},"zoom":{"mode":"x","wheel":{"enabled":true,"modifierKey":"ctrl"},"pinch":{"enabled":false},"drag":{"enabled":false}},"limits":{"x":{"min":"original","max":100.0}}}"#
+        );
+        let _guard = SettingsGuard::set(SerializeSettings{ transposed: true, ..SerializeSettings::default() });
+        assert!(to_js(&zoom).unwrap().ends_with(r#""limits":{"y":{"min":"original","max":100.0}}}"#));
+    }
+}
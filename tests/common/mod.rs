@@ -5,9 +5,9 @@ use sailfish::TemplateSimple;
 use tiny_http::{Header, Server};
 use chart_js_wrapper::render::OnePage;
 
-pub fn show_page(body: &str) {
+pub fn show_one_page(page: OnePage) {
     // Generate your HTML string here
-    let html = OnePage::new("Test",body).render_once().unwrap();
+    let html = page.render_once().unwrap();
 
     // Start a minimal web server in a separate thread
     let listener = TcpListener::bind("127.0.0.1:0").unwrap(); // Bind to any free port
@@ -1,10 +1,26 @@
-use crate::common::show_page;
-use chart_js_wrapper::common::Size;
-use chart_js_wrapper::{ChartConfig, ChartType, ScaleConfig};
+use crate::common::show_one_page;
+use chart_js_wrapper::common::{Color, Gradient, GradientDirection, Percent, Rgb, Size};
+use chart_js_wrapper::{AxisName, BorderConfig, Boundary, ChartConfig, ChartType, Dataset, DatasetStyle, Fill, FillVariant, GridConfig, Grace, PerTick, ScaleConfig, ScaleType, TickConfig, InteractionAxis, Tooltip, TooltipMode};
+use chart_js_wrapper::format::NumberFormat;
+use chart_js_wrapper::palette::{AutoColors, ColorLegend, ColorScale, Palette};
+use chart_js_wrapper::render::OnePage;
+use chart_js_wrapper::PointMeta;
+use chart_js_wrapper::interaction::{ClickConfig, LinkTarget};
+use chart_js_wrapper::tooltip::{ExternalTooltip, TooltipLayout, TooltipLine};
+use chart_js_wrapper::plugin::{InlinePlugin, PluginHook};
+use chart_js_wrapper::datalabels::{DataLabels, LabelAnchor, LabelAlign};
+use std::time::{Duration, Instant};
 use sailfish::TemplateSimple;
 
 mod common;
 
+#[derive(TemplateSimple)]
+#[template(path = "../tests/templates/incident_tooltip.stpl")]
+struct IncidentTooltip<'a> {
+    id: u32,
+    title: &'a str
+}
+
 #[test]
 fn show_chart() {
     let chart_y_cat = ChartConfig::<f64, &str>::default()
@@ -22,15 +38,22 @@ fn show_chart() {
 
         .build(Size::pixels(600),Size::pixels(400));
 
-    let chart_y_cat_bar = ChartConfig::<f64, &str>::default()
+    let chart_y_cat_bar = ChartConfig::<&str, f64>::default()
         .with_title("Bar only".into())
+        //horizontal bars, data is still (category, value)
+        .with_index_axis(AxisName::Y)
         .add_series(
             ChartType::Bar,
             "second_set".to_string(),
-            [(2.0,"Second"),(14.0,"Third"),(15.0,"Third"),(20.0,"Second")]
+            [("Second",2.0),("Third",14.0),("Third",15.0),("Second",20.0)]
         ).enable_legend()
-        //so, apparently, category labels for y should be reversed. why, oh why?
-        .set_y_axis(ScaleConfig::new_category(true,vec!["First","Second","Third","Fourth"]))
+        .with_data_labels(
+            DataLabels::default()
+                .with_anchor(LabelAnchor::End)
+                .with_align(LabelAlign::Start)
+                .with_format(NumberFormat::currency("USD").with_decimals(0))
+        )
+        .set_x_axis(ScaleConfig::new_category(false,vec!["First","Second","Third","Fourth"]))
         .build(Size::pixels(600),Size::pixels(400));
 
 
@@ -51,8 +74,20 @@ fn show_chart() {
             "third_set".to_string(),
             [("First",2.0),("Third",14.0),("Fifth",15.0),("First",20.0)]
         ).enable_legend()
-        //labels for x-axis should not be reversed, though, those are fine
         .set_x_axis(ScaleConfig::new_category(false,vec!["First","Second","Third","Fourth"]))
+        .set_y_axis(
+            ScaleConfig::default()
+                .with_type(ScaleType::Linear)
+                .with_begin_at_zero(true)
+                .with_grace(Grace::Percent(Percent(10.0)))
+                .with_ticks(TickConfig::default().with_step_size(2.5).with_format(NumberFormat::currency("EUR")))
+                .with_grid(
+                    GridConfig::default()
+                        .with_color(Rgb(220, 220, 220))
+                        .with_line_width(PerTick::ZeroLine{ zero: 3.0, other: 1.0 })
+                )
+                .with_border(BorderConfig::default().with_dash(vec![4.0, 4.0]))
+        )
         .build(Size::pixels(600),Size::pixels(400));
 
 
@@ -89,6 +124,13 @@ fn show_chart() {
                 (20.0,11.0)
             ]
         ).unwrap()
+        .with_auto_colors(AutoColors::new(Palette::OkabeIto))
+        .with_tooltip(Tooltip::default()
+            .with_mode(TooltipMode::Nearest)
+            .with_intersect(false)
+            .with_axis(InteractionAxis::X)
+            .with_corner_radius(2.0)
+            .with_box_size(8.0, 8.0))
         .build(Size::pixels(600),Size::pixels(400));
 
     let chart_with_custom_labels = ChartConfig::<f64, f64>::default()
@@ -115,11 +157,146 @@ fn show_chart() {
             ]
         )    .build(Size::pixels(600),Size::pixels(400));
 
-    let mut body = chart_y_cat.render_once().unwrap();
-    body.push_str(chart_y_cat_bar.render_once().unwrap().as_str());
-    body.push_str(chart_x_cat.render_once().unwrap().as_str());
-    body.push_str(numeric_chart.render_once().unwrap().as_str());
-    body.push_str(chart_with_custom_labels.render_once().unwrap().as_str());
+    let start = Instant::now();
+    let latency_chart = ChartConfig::<Duration, f64>::default()
+        .with_title("Since start".into())
+        .add_instant_series(
+            ChartType::Line,
+            "latency".to_string(),
+            Some(start),
+            (0..10).map(|i| (start + Duration::from_millis(i * 25_000), (i % 4) as f64)).collect::<Vec<_>>()
+        )
+        .build(Size::pixels(600),Size::pixels(400));
+
+    let crosshair = InlinePlugin::new("crosshair")
+        .with_hook(PluginHook::AfterEvent, "const e = args.event; chart.$crosshair = args.inChartArea && e.type !== 'mouseout' ? e.x : null; args.changed = true;")
+        .with_hook(PluginHook::AfterDatasetsDraw, "const x = chart.$crosshair; if (x == null) { return; } const a = chart.chartArea; const ctx = chart.ctx; \
+            ctx.save(); ctx.strokeStyle = options.color; ctx.setLineDash([4, 4]); ctx.beginPath(); ctx.moveTo(x, a.top); ctx.lineTo(x, a.bottom); ctx.stroke(); ctx.restore();");
+    let area_chart = ChartConfig::<f64, f64>::default()
+        .with_title("Gradient area".into())
+        .add_series_direct(
+            Dataset::new(ChartType::Line, "load".to_string(), (0..20).map(|i| (i as f64, ((i as f64) / 3.0).sin() + 1.5)).collect::<Vec<_>>())
+                .with_style(DatasetStyle::default()
+                    .with_fill(Fill::new(FillVariant::Boundary(Boundary::Origin)))
+                    .with_border_color(Color::named("steelblue").unwrap())
+                    .with_background_color(Gradient::new(GradientDirection::BottomToTop)
+                        .with_stop(0.0, Color::named("steelblue").unwrap().with_alpha(0.0))
+                        .with_stop(1.0, Color::named("steelblue").unwrap().with_alpha(0.6))))
+        )
+        .with_plugin_options(&crosshair, &serde_json::json!({"color": "gray"})).unwrap()
+        .build(Size::pixels(600),Size::pixels(400));
+
+    let error_rates = [0.1, 0.4, 2.5, 0.8, 4.0, 1.2];
+    let error_scale = ColorScale::red_green().with_domain(4.0, 0.0);
+    let error_chart = ChartConfig::<f64, f64>::default()
+        .with_title("Latency by error rate".into())
+        .add_series_direct(
+            Dataset::new(ChartType::Scatter, "services".to_string(), vec![
+                (120.0, 3.0, "auth"), (80.0, 5.0, "search"), (300.0, 2.0, "billing"),
+                (150.0, 7.0, "profile"), (420.0, 1.0, "export"), (200.0, 4.0, "feed")
+            ])
+                .with_style(DatasetStyle::default().with_point_radius(6.0))
+                .with_value_colors(&error_rates, &error_scale).unwrap()
+        )
+        .with_color_legend(ColorLegend::new("error rate", &error_scale, &error_rates).with_labels("4%", "0%"))
+        .build(Size::pixels(600),Size::pixels(400));
+
+    #[derive(serde::Serialize)]
+    struct Build { host: &'static str, commit: &'static str, duration: f64 }
+    let builds = [
+        (1.0, 4.5, Build { host: "ci-1", commit: "3f2a9c1", duration: 272_000.0 }),
+        (2.0, 3.9, Build { host: "ci-2", commit: "8be01d4", duration: 231_000.0 }),
+        (3.0, 6.1, Build { host: "ci-1", commit: "c41f7a0", duration: 368_000.0 }),
+    ];
+    let build_chart = ChartConfig::<f64, f64>::default()
+        .with_title("Build sizes".into())
+        .add_series(ChartType::Line, "size, MB".to_string(),
+            builds.iter().map(|(x, y, b)| (*x, *y, PointMeta::from_serialize(b).unwrap())).collect::<Vec<_>>())
+        .with_tooltip(Tooltip::default().with_layout(&TooltipLayout::new()
+            .with_title(TooltipLine::new().text("Commit ").field("commit"))
+            .with_line(TooltipLine::new().dataset_label().text(": ").value())
+            .with_line(TooltipLine::new().text("Took ").formatted_field("duration", NumberFormat::duration()))
+            .with_footer(TooltipLine::new().text("on ").field("host"))))
+        .build(Size::pixels(600),Size::pixels(400));
+
+    //tooltip HTML is rendered on the server, with links to follow from a point
+    let incident_meta = |id: u32, title: &str| PointMeta::new().with_tooltip_template(IncidentTooltip { id, title }).unwrap();
+    let incident_chart = ChartConfig::<f64, f64>::default()
+        .with_title("Incidents".into())
+        .add_series(ChartType::Scatter, "minutes to resolve".to_string(), vec![
+            (1.0, 35.0, incident_meta(101, "DNS outage").with_url("https://example.com/incidents/101")),
+            (2.0, 12.0, incident_meta(102, "Disk full").with_url("https://example.com/incidents/102")),
+            (3.0, 64.0, PointMeta::new())
+        ])
+        .with_tooltip(Tooltip::default().with_external(ExternalTooltip::new()))
+        .with_click(ClickConfig::new().with_target(LinkTarget::NewTab).with_event("incident-click"))
+        .build(Size::pixels(600),Size::pixels(400));
+
+    //charts are added through the page, so it loads the plugin scripts they need
+    let page = OnePage::new("Test", "")
+        .add_chart(chart_y_cat).unwrap()
+        .add_chart(chart_y_cat_bar).unwrap()
+        .add_chart(chart_x_cat).unwrap()
+        .add_chart(numeric_chart).unwrap()
+        .add_chart(chart_with_custom_labels).unwrap()
+        .add_chart(latency_chart).unwrap()
+        .add_chart(area_chart).unwrap()
+        .add_chart(error_chart).unwrap()
+        .add_chart(build_chart).unwrap()
+        .add_chart(incident_chart).unwrap();
+    show_one_page(page);
+}
+
+#[cfg(feature = "chrono_axis")]
+#[test]
+fn show_time_chart() {
+    use chart_js_wrapper::{TimeScaleOptions, TimeUnit};
+    use chart_js_wrapper::annotation::{AnnotationLabel, Annotations, BoxAnnotation, LineAnnotation};
+    use chart_js_wrapper::zoom::{LimitValue, ModifierKey, ScaleLimits, ZoomConfig, ZoomMode};
+    use chart_js_wrapper::time_zone::ChartTimeZone;
+    use chrono::{Duration, NaiveDate, Utc};
+
+    let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc();
+    let daily = ChartConfig::<chrono::DateTime<Utc>, f64>::default()
+        .with_title("Daily".into())
+        .with_time_zone(ChartTimeZone::fixed_hours(-5))
+        .add_series(
+            ChartType::Line,
+            "requests".to_string(),
+            (0..14).map(|d| (start + Duration::days(d), (d * d % 7) as f64)).collect::<Vec<_>>()
+        )
+        .set_x_axis(
+            ScaleConfig::default()
+                .with_type(ScaleType::Time)
+                .with_time(
+                    TimeScaleOptions::default()
+                        .with_unit(TimeUnit::Day)
+                        .with_display_format(TimeUnit::Day, "MMM d")
+                        .with_tooltip_format("yyyy-MM-dd")
+                )
+        )
+        .with_annotations(
+            Annotations::default()
+                .with_annotation("slo", LineAnnotation::horizontal(5.0)
+                    .with_border_color(Rgb(200, 0, 0))
+                    .with_border_dash(vec![6.0, 4.0])
+                    .with_label(AnnotationLabel::new("max 5 per day")))
+                .with_annotation("deploy", LineAnnotation::vertical(start + Duration::days(4))
+                    .with_label(AnnotationLabel::new("v2.1 deploy")))
+                .with_annotation("maintenance", BoxAnnotation::x_range(start + Duration::days(9), start + Duration::days(10))
+                    .with_background_color(Rgb(120, 120, 120).with_alpha(0.2)))
+        )
+        .with_zoom(
+            ZoomConfig::default()
+                .with_mode(ZoomMode::X)
+                .with_wheel(None)
+                .with_drag(Some(ModifierKey::Shift))
+                .with_pan(ZoomMode::X, None)
+                .with_x_limits(ScaleLimits::default().with_min(LimitValue::Original).with_max(LimitValue::Original))
+                .with_reset_button("Reset zoom")
+        )
+        .build(Size::pixels(600),Size::pixels(400));
 
-    show_page(&body);
-}
\ No newline at end of file
+    let page = OnePage::new("Test", "").add_chart(daily).unwrap();
+    show_one_page(page);
+}